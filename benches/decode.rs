@@ -0,0 +1,35 @@
+use alloy_primitives::aliases::U256;
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use eth_abi::codec::types::ValueBuilder;
+use eth_abi::decode::abi_decode;
+use eth_abi::encode::abi_encode;
+
+fn decode_uint256_array(c: &mut Criterion) {
+    let numbers: Vec<U256> = (0..10_000u64).map(U256::from).collect();
+    let values = ValueBuilder::new().add_array(numbers).build();
+    let encoded = abi_encode(&["uint256[]"], &values).unwrap();
+
+    c.bench_function("decode uint256[10000]", |b| {
+        b.iter(|| abi_decode(black_box(&["uint256[]"]), black_box(&encoded)).unwrap())
+    });
+}
+
+fn decode_mixed_types(c: &mut Criterion) {
+    let type_strs = ["address", "uint8", "int128", "bytes4", "bool", "string"];
+    let values = ValueBuilder::new()
+        .add(alloy_primitives::Address::repeat_byte(0x11))
+        .add(alloy_primitives::aliases::U8::from(7))
+        .add(alloy_primitives::aliases::I128::MINUS_ONE)
+        .add(alloy_primitives::FixedBytes::<4>::repeat_byte(0xab))
+        .add(true)
+        .add(String::from("benchmark"))
+        .build();
+    let encoded = abi_encode(&type_strs, &values).unwrap();
+
+    c.bench_function("decode mixed types", |b| {
+        b.iter(|| abi_decode(black_box(&type_strs), black_box(&encoded)).unwrap())
+    });
+}
+
+criterion_group!(benches, decode_uint256_array, decode_mixed_types);
+criterion_main!(benches);
@@ -0,0 +1,117 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+#[proc_macro_derive(AbiDecode)]
+pub fn derive_abi_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match named_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let field_count = fields.len();
+    let field_values = fields.iter().enumerate().map(|(i, field)| {
+        let field_name = &field.ident;
+        let field_type = &field.ty;
+        quote! {
+            #field_name: <#field_type as ::eth_abi::codec::traits::FromValue>::from_value(&values[#i])?
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::eth_abi::codec::traits::FromValue for #name #ty_generics #where_clause {
+            fn from_value(
+                value: &::eth_abi::codec::types::Value,
+            ) -> Result<Self, ::eth_abi::errors::CodecError> {
+                let values = match value {
+                    ::eth_abi::codec::types::Value::Collection(values) => values,
+                    ::eth_abi::codec::types::Value::Single(_, _) => {
+                        return Err(::eth_abi::errors::CodecError::LengthsMismatch(#field_count, 1));
+                    }
+                };
+                if values.len() != #field_count {
+                    return Err(::eth_abi::errors::CodecError::LengthsMismatch(
+                        #field_count,
+                        values.len(),
+                    ));
+                }
+
+                Ok(Self {
+                    #(#field_values),*
+                })
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn from_calldata(
+                signature: &str,
+                data: &[u8],
+            ) -> Result<Self, ::eth_abi::errors::CodecError> {
+                let values = ::eth_abi::decode::abi_decode_with_signature(signature, data)?;
+                <Self as ::eth_abi::codec::traits::FromValue>::from_value(
+                    &::eth_abi::codec::types::Value::Collection(values),
+                )
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(AbiEncode)]
+pub fn derive_abi_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match named_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let field_values: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            let field_name = &field.ident;
+            quote! {
+                ::eth_abi::codec::traits::ToValue::to_value(&self.#field_name)
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #impl_generics ::eth_abi::codec::traits::ToValue for #name #ty_generics #where_clause {
+            fn to_value(&self) -> ::eth_abi::codec::types::Value {
+                ::eth_abi::codec::types::Value::Collection(vec![#(#field_values),*])
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn to_calldata(
+                &self,
+                signature: &str,
+            ) -> Result<Vec<u8>, ::eth_abi::errors::CodecError> {
+                let values = vec![#(#field_values),*];
+                ::eth_abi::encode::abi_encode_with_singature(signature, &values)
+            }
+        }
+    }
+    .into()
+}
+
+fn named_fields(input: &DeriveInput) -> syn::Result<Vec<&syn::Field>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter().collect()),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "only structs with named fields are supported",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs with named fields are supported",
+        )),
+    }
+}
@@ -0,0 +1,113 @@
+use alloy_primitives::{Address, Bytes, U256, address, hex};
+use eth_abi::codec::types::ValueBuilder;
+use eth_abi::encode::abi_encode_with_singature;
+use eth_abi::errors::CodecError;
+use eth_abi_derive::{AbiDecode, AbiEncode};
+
+#[derive(Debug, PartialEq, AbiDecode, AbiEncode)]
+struct Transfer {
+    to: Address,
+    amount: U256,
+}
+
+#[derive(Debug, PartialEq, AbiDecode, AbiEncode)]
+struct Order {
+    maker: Address,
+    data: Bytes,
+}
+
+#[derive(Debug, PartialEq, AbiDecode, AbiEncode)]
+struct Submit {
+    order: Order,
+    ids: Vec<U256>,
+    note: String,
+    valid: bool,
+}
+
+#[test]
+fn test_derive_abi_decode() {
+    let calldata = hex!(
+        "a9059cbb000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa960450000000000000000000000000000000000000000000000000de0b6b3a7640000"
+    );
+
+    let transfer = Transfer::from_calldata("transfer(address,uint256)", &calldata).unwrap();
+    assert_eq!(
+        transfer,
+        Transfer {
+            to: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+            amount: U256::from(1_000_000_000_000_000_000u64),
+        }
+    );
+}
+
+#[test]
+fn test_derive_abi_decode_nested() {
+    let signature = "submit((address,bytes),uint256[],string,bool)";
+    let values = ValueBuilder::new()
+        .add_tuple(vec![
+            Box::new(Address::repeat_byte(0x11)),
+            Box::new(Bytes::from(hex!("deadbeef"))),
+        ])
+        .add_array(vec![U256::from(1), U256::from(2)])
+        .add(String::from("Hello, world!"))
+        .add(true)
+        .build();
+    let calldata = abi_encode_with_singature(signature, &values).unwrap();
+
+    let submit = Submit::from_calldata(signature, &calldata).unwrap();
+    assert_eq!(
+        submit,
+        Submit {
+            order: Order {
+                maker: Address::repeat_byte(0x11),
+                data: Bytes::from(hex!("deadbeef")),
+            },
+            ids: vec![U256::from(1), U256::from(2)],
+            note: String::from("Hello, world!"),
+            valid: true,
+        }
+    );
+}
+
+#[test]
+fn test_derive_abi_decode_arity_mismatch() {
+    let signature = "transfer(address)";
+    let values = ValueBuilder::new().add(Address::ZERO).build();
+    let calldata = abi_encode_with_singature(signature, &values).unwrap();
+
+    assert_eq!(
+        Transfer::from_calldata(signature, &calldata),
+        Err(CodecError::LengthsMismatch(2, 1))
+    );
+}
+
+#[test]
+fn test_derive_abi_encode() {
+    let transfer = Transfer {
+        to: address!("d8da6bf26964af9d7eed9e03e53415d37aa96045"),
+        amount: U256::from(1_000_000_000_000_000_000u64),
+    };
+
+    let calldata = transfer.to_calldata("transfer(address,uint256)").unwrap();
+    assert_eq!(
+        hex::encode(&calldata),
+        "a9059cbb000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa960450000000000000000000000000000000000000000000000000de0b6b3a7640000"
+    );
+}
+
+#[test]
+fn test_derive_abi_encode_round_trip() {
+    let signature = "submit((address,bytes),uint256[],string,bool)";
+    let submit = Submit {
+        order: Order {
+            maker: Address::repeat_byte(0x11),
+            data: Bytes::from(hex!("deadbeef")),
+        },
+        ids: vec![U256::from(1), U256::from(2)],
+        note: String::from("Hello, world!"),
+        valid: true,
+    };
+
+    let calldata = submit.to_calldata(signature).unwrap();
+    assert_eq!(Submit::from_calldata(signature, &calldata).unwrap(), submit);
+}
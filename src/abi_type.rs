@@ -0,0 +1,156 @@
+use crate::common::split_parameter_types;
+use crate::errors::CodecError;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiType {
+    Uint(usize),
+    Int(usize),
+    Address,
+    Bool,
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Function,
+    Array(Box<AbiType>),
+    FixedArray(Box<AbiType>, usize),
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(inner, _) => inner.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiType::Uint(bits) => write!(f, "uint{}", bits),
+            AbiType::Int(bits) => write!(f, "int{}", bits),
+            AbiType::Address => write!(f, "address"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::FixedBytes(size) => write!(f, "bytes{}", size),
+            AbiType::Bytes => write!(f, "bytes"),
+            AbiType::String => write!(f, "string"),
+            AbiType::Function => write!(f, "function"),
+            AbiType::Array(inner) => write!(f, "{}[]", inner),
+            AbiType::FixedArray(inner, size) => write!(f, "{}[{}]", inner, size),
+            AbiType::Tuple(types) => write!(f, "({})", join_types(types)),
+        }
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = CodecError;
+
+    fn from_str(t: &str) -> Result<Self, Self::Err> {
+        let t = t.trim();
+
+        if let Some(inner) = t.strip_suffix(']') {
+            let open_bracket_index = inner
+                .rfind('[')
+                .ok_or_else(|| CodecError::InvalidArray(t.to_string()))?;
+            let element_type = inner[..open_bracket_index].parse()?;
+            let size = &inner[open_bracket_index + 1..];
+            if size.is_empty() {
+                return Ok(AbiType::Array(Box::new(element_type)));
+            }
+
+            let size = size
+                .parse()
+                .map_err(|_| CodecError::InvalidArray(t.to_string()))?;
+            return Ok(AbiType::FixedArray(Box::new(element_type), size));
+        }
+
+        if t.starts_with('(') && t.ends_with(')') {
+            let types = split_parameter_types(t)
+                .into_iter()
+                .map(|t| t.parse())
+                .collect::<Result<Vec<AbiType>, CodecError>>()?;
+            return Ok(AbiType::Tuple(types));
+        }
+
+        match t {
+            "address" => return Ok(AbiType::Address),
+            "bool" => return Ok(AbiType::Bool),
+            "bytes" => return Ok(AbiType::Bytes),
+            "string" => return Ok(AbiType::String),
+            "function" => return Ok(AbiType::Function),
+            "uint" => return Ok(AbiType::Uint(256)),
+            "int" => return Ok(AbiType::Int(256)),
+            _ => {}
+        }
+
+        let parse_size = |size: &str, max: usize, step: usize| match size.parse::<usize>() {
+            Ok(size) if size > 0 && size <= max && size % step == 0 => Ok(size),
+            _ => Err(CodecError::UnsupportedType(t.to_string())),
+        };
+
+        if let Some(bits) = t.strip_prefix("uint") {
+            Ok(AbiType::Uint(parse_size(bits, 256, 8)?))
+        } else if let Some(bits) = t.strip_prefix("int") {
+            Ok(AbiType::Int(parse_size(bits, 256, 8)?))
+        } else if let Some(size) = t.strip_prefix("bytes") {
+            Ok(AbiType::FixedBytes(parse_size(size, 32, 1)?))
+        } else {
+            Err(CodecError::UnsupportedType(t.to_string()))
+        }
+    }
+}
+
+pub fn signature_from_types(name: &str, types: &[AbiType]) -> String {
+    format!("{}({})", name, join_types(types))
+}
+
+fn join_types(types: &[AbiType]) -> String {
+    types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod abi_type_tests {
+    use super::*;
+    use crate::encode::abi_encode_selector;
+
+    #[test]
+    fn test_signature_from_types() {
+        let types = vec![
+            AbiType::Array(Box::new(AbiType::Tuple(vec![
+                AbiType::Uint(256),
+                AbiType::Array(Box::new(AbiType::Address)),
+            ]))),
+            AbiType::FixedArray(Box::new(AbiType::FixedBytes(32)), 2),
+            AbiType::Bytes,
+        ];
+
+        let signature = signature_from_types("foo", &types);
+        assert_eq!(signature, "foo((uint256,address[])[],bytes32[2],bytes)");
+        assert_eq!(
+            abi_encode_selector(&signature).unwrap(),
+            abi_encode_selector("foo((uint256,address[])[],bytes32[2],bytes)").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_abi_type_parse() {
+        let t = "((uint256,address[])[],bytes32[2],bytes)";
+        let abi_type: AbiType = t.parse().unwrap();
+        assert_eq!(abi_type.to_string(), t);
+        assert!(abi_type.is_dynamic());
+        assert_eq!("uint".parse(), Ok(AbiType::Uint(256)));
+        assert_eq!(
+            "uint7".parse::<AbiType>(),
+            Err(CodecError::UnsupportedType("uint7".to_string()))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::codec::traits::{EncodeCodec, FromValue};
+use crate::codec::types::Value;
+use crate::common::get_parameter_types;
+use crate::decode::{DecodeOptions, abi_decode_with_options};
+use crate::encode::{abi_encode_with_max_depth, try_abi_encode_selector_array};
+use crate::errors::CodecError;
+use crate::registry::TypeRegistry;
+use alloy_primitives::{Address, hex};
+use std::sync::Arc;
+
+// Encoder/decoder configured once and reused. `Codec::default()` behaves like
+// the free functions; `Codec::strict()` validates offsets and padding.
+#[derive(Debug, Clone, Default)]
+pub struct Codec {
+    options: DecodeOptions,
+    address_format: AddressFormat,
+    registry: Option<Arc<TypeRegistry>>,
+}
+
+// how `Codec::format` renders addresses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressFormat {
+    // EIP-55 mixed case, as `Value::to_string` prints them
+    #[default]
+    Checksummed,
+    Lowercase,
+}
+
+impl Codec {
+    pub fn new(options: DecodeOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    pub fn strict() -> Self {
+        Self::new(DecodeOptions::strict())
+    }
+
+    pub fn max_array_len(mut self, max_array_len: usize) -> Self {
+        self.options.max_array_len = Some(max_array_len);
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.options.max_depth = max_depth;
+        self
+    }
+
+    pub fn address_format(mut self, address_format: AddressFormat) -> Self {
+        self.address_format = address_format;
+        self
+    }
+
+    // custom type names are resolved through the registry on every encode
+    // and decode
+    pub fn with_registry(mut self, registry: Arc<TypeRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    pub fn options(&self) -> &DecodeOptions {
+        &self.options
+    }
+
+    // `Value::to_string` with addresses in the configured format
+    pub fn format(&self, value: &Value) -> String {
+        match value {
+            Value::Single(_, type_str) if type_str == "address" => {
+                match (Address::from_value(value), self.address_format) {
+                    (Ok(address), AddressFormat::Lowercase) => hex::encode_prefixed(address),
+                    _ => value.to_string(),
+                }
+            }
+            Value::Single(_, _) => value.to_string(),
+            Value::Collection(values) => values
+                .iter()
+                .map(|v| self.format(v))
+                .collect::<Vec<String>>()
+                .join(", "),
+        }
+    }
+
+    pub fn encode(&self, type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+        let Some(registry) = &self.registry else {
+            return abi_encode_with_max_depth(type_strs, values, self.options.max_depth);
+        };
+
+        let (builtin_types, builtin_values) = registry.lower(type_strs, values)?;
+        let builtin_types: Vec<&str> = builtin_types.iter().map(|t| t.as_str()).collect();
+        abi_encode_with_max_depth(&builtin_types, &builtin_values, self.options.max_depth)
+    }
+
+    pub fn decode(
+        &self,
+        type_strs: &[&str],
+        encoded_values: &[u8],
+    ) -> Result<Vec<Value>, CodecError> {
+        let Some(registry) = &self.registry else {
+            return abi_decode_with_options(type_strs, encoded_values, &self.options);
+        };
+
+        let builtin_types = registry.resolve_all(type_strs);
+        let builtin_types: Vec<&str> = builtin_types.iter().map(|t| t.as_str()).collect();
+        let values = abi_decode_with_options(&builtin_types, encoded_values, &self.options)?;
+        registry.restore(type_strs, values)
+    }
+
+    pub fn decode_with_signature(
+        &self,
+        signature: &str,
+        encoded_values: &[u8],
+    ) -> Result<Vec<Value>, CodecError> {
+        let selector = try_abi_encode_selector_array(signature)?;
+        let type_strs = get_parameter_types(signature)?;
+        if encoded_values.get(..4) != Some(&selector[..]) {
+            return Err(CodecError::InvalidSelector);
+        }
+
+        self.decode(&type_strs, &encoded_values[4..])
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::codec::traits::FromValue;
+    use crate::codec::types::ValueBuilder;
+    use crate::encode::{abi_encode, abi_encode_with_singature};
+    use alloy_primitives::{Address, U256};
+
+    #[test]
+    fn test_strict_codec() {
+        let codec = Codec::strict();
+        let signature = "transfer(address,uint256)";
+        let address = Address::repeat_byte(0x77);
+        let values = ValueBuilder::new().add(address).add(U256::from(5)).build();
+
+        let clean = abi_encode_with_singature(signature, &values).unwrap();
+        let decoded = codec.decode_with_signature(signature, &clean).unwrap();
+        assert_eq!(Address::from_value(&decoded[0]).unwrap(), address);
+        assert_eq!(
+            codec.encode(&["address", "uint256"], &decoded).unwrap(),
+            clean[4..]
+        );
+
+        // a set bit in the address's padding
+        let mut dirty = clean.clone();
+        dirty[4] = 0x01;
+        assert_eq!(
+            codec.decode_with_signature(signature, &dirty).unwrap_err(),
+            CodecError::DirtyPaddingBytes
+        );
+        assert!(
+            Codec::default()
+                .decode_with_signature(signature, &dirty)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_codec_limits() {
+        let codec = Codec::default().max_array_len(1).max_depth(1);
+        let encoded = abi_encode(
+            &["uint256[]"],
+            &ValueBuilder::new()
+                .add_array(vec![U256::from(1); 2])
+                .build(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            codec.decode(&["uint256[]"], &encoded).unwrap_err(),
+            CodecError::InvalidValueLength(2)
+        );
+        assert_eq!(
+            codec
+                .encode(&["uint256[][]"], &[Value::new(Vec::new())])
+                .unwrap_err(),
+            CodecError::RecursionLimitExceeded(2)
+        );
+    }
+
+    #[test]
+    fn test_address_format() {
+        let address = Address::repeat_byte(0xab);
+        let values = ValueBuilder::new()
+            .add(address)
+            .add_array(vec![address])
+            .add(U256::from(5))
+            .build();
+        let value = Value::new(values);
+
+        assert_eq!(Codec::default().format(&value), value.to_string());
+        let lowercase = format!("0x{}", "ab".repeat(20));
+        assert_eq!(
+            Codec::strict()
+                .address_format(AddressFormat::Lowercase)
+                .format(&value),
+            format!("{}, {}, 5", lowercase, lowercase)
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::codec::traits::{DecodeCodec, EncodeCodec};
+use crate::codec::traits::{DecodeCodec, EncodeCodec, FromValue, ToValue};
+use crate::codec::types::Value;
+use crate::codec::utils::{downcast_single, to_single};
+use crate::errors::CodecError;
 use alloy_primitives::{Address, Bytes, FixedBytes, hex};
 use std::any::Any;
 
@@ -30,6 +33,18 @@ impl<const N: usize> DecodeCodec for FixedBytes<N> {
     }
 }
 
+impl<const N: usize> FromValue for FixedBytes<N> {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl<const N: usize> ToValue for FixedBytes<N> {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
+
 impl EncodeCodec for String {
     fn to_bytes_vec(&self) -> Vec<u8> {
         self.as_bytes().to_vec()
@@ -58,6 +73,18 @@ impl DecodeCodec for String {
     }
 }
 
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
+
 impl EncodeCodec for Address {
     fn to_bytes_vec(&self) -> Vec<u8> {
         self.into_array().to_vec()
@@ -86,6 +113,18 @@ impl DecodeCodec for Address {
     }
 }
 
+impl FromValue for Address {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl ToValue for Address {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
+
 impl EncodeCodec for Bytes {
     fn to_bytes_vec(&self) -> Vec<u8> {
         self.to_vec()
@@ -114,6 +153,18 @@ impl DecodeCodec for Bytes {
     }
 }
 
+impl FromValue for Bytes {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl ToValue for Bytes {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
+
 impl EncodeCodec for bool {
     fn to_bytes_vec(&self) -> Vec<u8> {
         vec![*self as u8]
@@ -141,3 +192,15 @@ impl DecodeCodec for bool {
         bytes[0] != 0
     }
 }
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
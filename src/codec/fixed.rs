@@ -0,0 +1,221 @@
+use crate::codec::traits::{EncodeCodec, FromValue, ToValue};
+use crate::codec::types::Value;
+use crate::codec::utils::{downcast_single, to_single};
+use crate::common::parse_fixed_type;
+use crate::errors::CodecError;
+use alloy_primitives::aliases::U256;
+use std::any::Any;
+
+// A `fixedMxN` or `ufixedMxN` number: the value times 10^N, held as the
+// sign-extended word it is encoded as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPoint {
+    word: U256,
+    signed: bool,
+    bits: usize,
+    decimals: usize,
+}
+
+impl FixedPoint {
+    // parses a decimal such as `-1.25` into the given fixed-point type; more
+    // fractional digits than the type has, or a value outside M bits, fail
+    pub fn from_decimal(type_str: &str, decimal: &str) -> Result<Self, CodecError> {
+        let (signed, bits, decimals) = parse_fixed_type(type_str)
+            .ok_or_else(|| CodecError::UnsupportedType(type_str.to_string()))?;
+        let invalid = || CodecError::InvalidTypeAndValue(type_str.to_string(), decimal.to_string());
+
+        let (negative, digits) = match decimal.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, decimal),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if (integer.is_empty() && fraction.is_empty())
+            || fraction.len() > decimals
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+            || (negative && !signed)
+        {
+            return Err(invalid());
+        }
+
+        let scaled = format!("{}{:0<width$}", integer, fraction, width = decimals);
+        let magnitude = U256::from_str_radix(&scaled, 10).map_err(|_| invalid())?;
+        // signed types reach one further below zero than above it
+        let fits = if !signed {
+            bits == 256 || magnitude >> bits == U256::ZERO
+        } else if negative {
+            magnitude <= U256::from(1) << (bits - 1)
+        } else {
+            magnitude < U256::from(1) << (bits - 1)
+        };
+        if !fits {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            word: if negative {
+                magnitude.wrapping_neg()
+            } else {
+                magnitude
+            },
+            signed,
+            bits,
+            decimals,
+        })
+    }
+
+    // reads the low M/8 bytes of a big-endian encoding, or None when the
+    // type isn't fixed-point or the input is too short
+    pub fn from_be_slice(type_str: &str, bytes: &[u8]) -> Option<Self> {
+        let (signed, bits, decimals) = parse_fixed_type(type_str)?;
+        let bytes = bytes.get(..bits / 8)?;
+
+        let fill = if signed && bytes[0] & 0x80 != 0 {
+            0xff
+        } else {
+            0x00
+        };
+        let mut word = [fill; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+
+        Some(Self {
+            word: U256::from_be_bytes(word),
+            signed,
+            bits,
+            decimals,
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.signed && self.word.bit(255)
+    }
+}
+
+impl EncodeCodec for FixedPoint {
+    fn to_bytes_vec(&self) -> Vec<u8> {
+        self.word.to_be_bytes::<32>()[32 - self.bytes_length()..].to_vec()
+    }
+
+    fn bytes_length(&self) -> usize {
+        self.bits / 8
+    }
+
+    fn eth_type(&self) -> String {
+        let prefix = if self.signed { "" } else { "u" };
+        format!("{}fixed{}x{}", prefix, self.bits, self.decimals)
+    }
+
+    // the scaled integer with the decimal point put back, e.g. `1.5`
+    fn to_string(&self) -> String {
+        let magnitude = if self.is_negative() {
+            self.word.wrapping_neg()
+        } else {
+            self.word
+        };
+        let digits = format!("{:0>width$}", magnitude, width = self.decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.decimals);
+        let fraction = fraction.trim_end_matches('0');
+
+        let sign = if self.is_negative() { "-" } else { "" };
+        if fraction.is_empty() {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}.{}", sign, integer, fraction)
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl FromValue for FixedPoint {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        downcast_single(value)
+    }
+}
+
+impl ToValue for FixedPoint {
+    fn to_value(&self) -> Value {
+        to_single(self)
+    }
+}
+
+#[cfg(test)]
+mod fixed_tests {
+    use super::*;
+    use crate::codec::types::ValueBuilder;
+    use crate::decode::abi_decode;
+    use crate::encode::abi_encode;
+
+    #[test]
+    fn test_ufixed_round_trip() {
+        let value = FixedPoint::from_decimal("ufixed128x18", "1.5").unwrap();
+        let values = ValueBuilder::new().add(value).build();
+
+        let encoded = abi_encode(&["ufixed128x18"], &values).unwrap();
+        let scaled = U256::from(15) * U256::from(10).pow(U256::from(17));
+        assert_eq!(encoded, scaled.to_be_bytes::<32>());
+
+        let decoded = abi_decode(&["ufixed128x18"], &encoded).unwrap();
+        assert_eq!(decoded[0].eth_type(), "ufixed128x18");
+        assert_eq!(decoded[0].to_string(), "1.5");
+        assert_eq!(FixedPoint::from_value(&decoded[0]), Ok(value));
+    }
+
+    #[test]
+    fn test_fixed_alias_and_sign() {
+        let value = FixedPoint::from_decimal("fixed", "-0.25").unwrap();
+        assert_eq!(value.eth_type(), "fixed128x18");
+
+        let values = ValueBuilder::new().add(value).build();
+        let encoded = abi_encode(&["fixed"], &values).unwrap();
+        assert_eq!(encoded[..16], [0xff; 16]);
+
+        let decoded = abi_decode(&["fixed"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "-0.25");
+        assert_eq!(
+            FixedPoint::from_decimal("fixed8x0", "-128")
+                .unwrap()
+                .to_string(),
+            "-128"
+        );
+    }
+
+    #[test]
+    fn test_from_decimal_rejects() {
+        let invalid = |t: &str, d: &str| {
+            Err(CodecError::InvalidTypeAndValue(
+                t.to_string(),
+                d.to_string(),
+            ))
+        };
+
+        assert_eq!(
+            FixedPoint::from_decimal("ufixed8x1", "0.25"),
+            invalid("ufixed8x1", "0.25")
+        );
+        assert_eq!(
+            FixedPoint::from_decimal("ufixed8x1", "25.6"),
+            invalid("ufixed8x1", "25.6")
+        );
+        assert_eq!(
+            FixedPoint::from_decimal("ufixed", "-1"),
+            invalid("ufixed", "-1")
+        );
+        assert_eq!(
+            FixedPoint::from_decimal("fixed8x0", "128"),
+            invalid("fixed8x0", "128")
+        );
+        assert_eq!(
+            FixedPoint::from_decimal("fixed", "1.2.3"),
+            invalid("fixed", "1.2.3")
+        );
+        assert_eq!(
+            FixedPoint::from_decimal("fixed7x1", "1"),
+            Err(CodecError::UnsupportedType("fixed7x1".to_string()))
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::codec::traits::BoxTrait;
 use crate::codec::traits::EncodeCodec;
-use crate::codec::types::Value;
+use crate::codec::traits::FromValue;
+use crate::codec::traits::ToValue;
+use crate::codec::types::{SharedValue, Value};
+use crate::errors::CodecError;
 use std::any::Any;
 use std::fmt::Debug;
 
@@ -18,9 +21,7 @@ impl EncodeCodec for Value {
     fn to_bytes_vec(&self) -> Vec<u8> {
         match self {
             Value::Single(value, _) => value.to_bytes_vec(),
-            Value::Collection(values) => {
-                values.iter().map(|v| v.to_bytes_vec()).flatten().collect()
-            }
+            Value::Collection(values) => values.iter().flat_map(|v| v.to_bytes_vec()).collect(),
         }
     }
 
@@ -109,8 +110,45 @@ impl EncodeCodec for Vec<Box<dyn BoxTrait>> {
     }
 }
 
+impl EncodeCodec for SharedValue {
+    fn to_bytes_vec(&self) -> Vec<u8> {
+        self.0.to_bytes_vec()
+    }
+
+    fn bytes_length(&self) -> usize {
+        self.0.bytes_length()
+    }
+
+    fn eth_type(&self) -> String {
+        self.0.eth_type()
+    }
+
+    fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.0.as_any()
+    }
+}
+
 impl Clone for Box<dyn BoxTrait> {
     fn clone(&self) -> Self {
         self.clone_box()
     }
 }
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self, CodecError> {
+        match value {
+            Value::Collection(values) => values.iter().map(T::from_value).collect(),
+            Value::Single(_, type_str) => Err(CodecError::InvalidArray(type_str.clone())),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Vec<T> {
+    fn to_value(&self) -> Value {
+        Value::Collection(self.iter().map(|v| v.to_value()).collect())
+    }
+}
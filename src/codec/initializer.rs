@@ -1,4 +1,7 @@
-use crate::codec::traits::{DecodeCodec, EncodeCodec};
+use crate::codec::traits::{DecodeCodec, EncodeCodec, FromValue, ToValue};
+use crate::codec::types::Value;
+use crate::codec::utils::{downcast_single, to_single};
+use crate::errors::CodecError;
 use alloy_primitives::aliases::*;
 use std::any::Any;
 
@@ -44,6 +47,24 @@ macro_rules! impl_decode_codec_for_uint_and_int {
     };
 }
 
+macro_rules! impl_value_conversions_for_uint_and_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &Value) -> Result<Self, CodecError> {
+                    downcast_single(value)
+                }
+            }
+
+            impl ToValue for $t {
+                fn to_value(&self) -> Value {
+                    to_single(self)
+                }
+            }
+        )*
+    };
+}
+
 impl_encode_codec_for_uint_and_int!(
     U8, U16, U24, U32, U40, U48, U56, U64, U72, U80, U88, U96, U104, U112, U120, U128, U136, U144,
     U152, U160, U168, U176, U184, U192, U200, U208, U216, U224, U232, U240, U248, U256, I8, I16,
@@ -57,3 +78,10 @@ impl_decode_codec_for_uint_and_int!(
     I24, I32, I40, I48, I56, I64, I72, I80, I88, I96, I104, I112, I120, I128, I136, I144, I152,
     I160, I168, I176, I184, I192, I200, I208, I216, I224, I232, I240, I248, I256
 );
+
+impl_value_conversions_for_uint_and_int!(
+    U8, U16, U24, U32, U40, U48, U56, U64, U72, U80, U88, U96, U104, U112, U120, U128, U136, U144,
+    U152, U160, U168, U176, U184, U192, U200, U208, U216, U224, U232, U240, U248, U256, I8, I16,
+    I24, I32, I40, I48, I56, I64, I72, I80, I88, I96, I104, I112, I120, I128, I136, I144, I152,
+    I160, I168, I176, I184, I192, I200, I208, I216, I224, I232, I240, I248, I256
+);
@@ -0,0 +1,280 @@
+use crate::codec::types::{Value, create_value};
+use crate::common::{
+    get_array_element_type, get_bytes_from_type, is_array, is_fixed_bytes, is_tuple,
+};
+use crate::decode::abi_decode;
+use crate::errors::CodecError;
+use alloy_primitives::aliases::{I256, U256};
+use alloy_primitives::{Address, Bytes, hex};
+use std::str::FromStr;
+
+impl Value {
+    // Parses the literal forms `to_solidity_literal` emits back into a value
+    // of the given type. Type casts like `uint8(1)` may be left out.
+    pub fn parse_solidity_literal(type_str: &str, literal: &str) -> Result<Value, CodecError> {
+        let literal = literal.trim();
+        let invalid = || CodecError::InvalidTypeAndValue(type_str.to_string(), literal.to_string());
+
+        if is_array(type_str)?.0 {
+            let element_type = get_array_element_type(type_str);
+            let inner = strip_delimiters(literal, '[', ']').ok_or_else(invalid)?;
+            return split_literal_list(inner)
+                .into_iter()
+                .map(|element| Value::parse_solidity_literal(element_type, element))
+                .collect::<Result<Vec<Value>, CodecError>>()
+                .map(Value::Collection);
+        }
+
+        let (is_tuple_type, tuple_types) = is_tuple(type_str)?;
+        if is_tuple_type {
+            let inner = strip_delimiters(literal, '(', ')').ok_or_else(invalid)?;
+            let members = split_literal_list(inner);
+            if members.len() != tuple_types.len() {
+                return Err(CodecError::LengthsMismatch(
+                    tuple_types.len(),
+                    members.len(),
+                ));
+            }
+            return tuple_types
+                .iter()
+                .zip(members)
+                .map(|(t, member)| Value::parse_solidity_literal(t, member))
+                .collect::<Result<Vec<Value>, CodecError>>()
+                .map(Value::Collection);
+        }
+
+        let literal = strip_cast(literal, type_str);
+        match type_str {
+            // read back through a word so the value must fit the width
+            t if t.starts_with("uint") => {
+                let value = U256::from_str_radix(literal, 10).map_err(|_| invalid())?;
+                Value::int_from_word(t, value.to_be_bytes())
+            }
+            t if t.starts_with("int") => {
+                let value = I256::from_dec_str(literal).map_err(|_| invalid())?;
+                Value::int_from_word(t, value.to_be_bytes())
+            }
+            "address" => Address::from_str(literal)
+                .map(|v| create_value(v, "address"))
+                .map_err(|_| invalid()),
+            "bool" => match literal {
+                "true" => Ok(create_value(true, "bool")),
+                "false" => Ok(create_value(false, "bool")),
+                _ => Err(invalid()),
+            },
+            "bytes" => {
+                let bytes = parse_hex_literal(literal).ok_or_else(invalid)?;
+                Ok(create_value(Bytes::from(bytes), "bytes"))
+            }
+            "string" => parse_string_literal(literal)
+                .map(|v| create_value(v, "string"))
+                .ok_or_else(invalid),
+            t if is_fixed_bytes(t) => {
+                let bytes = parse_hex_literal(literal).ok_or_else(invalid)?;
+                if bytes.len() != get_bytes_from_type(t) {
+                    return Err(invalid());
+                }
+                // decoded from its right-padded word
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(abi_decode(&[t], &word)?.remove(0))
+            }
+            _ => Err(CodecError::UnsupportedType(type_str.to_string())),
+        }
+    }
+}
+
+fn strip_delimiters(literal: &str, open: char, close: char) -> Option<&str> {
+    literal.strip_prefix(open)?.strip_suffix(close)
+}
+
+// `uint8(5)` -> `5`, `bytes4(hex"..")` -> `hex".."`; a bare literal is kept
+fn strip_cast<'a>(literal: &'a str, type_str: &str) -> &'a str {
+    let cast_type = if type_str == "function" {
+        "bytes24"
+    } else {
+        type_str
+    };
+    literal
+        .strip_prefix(cast_type)
+        .and_then(|rest| strip_delimiters(rest, '(', ')'))
+        .unwrap_or(literal)
+}
+
+// splits on commas outside of quotes, brackets and parentheses
+fn split_literal_list(list: &str) -> Vec<&str> {
+    if list.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut parts = Vec::new();
+    let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '[' | '(' if !in_string => depth += 1,
+            ']' | ')' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                parts.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(list[start..].trim());
+
+    parts
+}
+
+fn parse_hex_literal(literal: &str) -> Option<Vec<u8>> {
+    let digits = literal
+        .strip_prefix("hex\"")
+        .and_then(|rest| rest.strip_suffix('"'))
+        .or_else(|| literal.strip_prefix("0x"))?;
+
+    hex::decode(digits).ok()
+}
+
+fn parse_string_literal(literal: &str) -> Option<String> {
+    let quoted = literal.strip_prefix("unicode").unwrap_or(literal);
+    let inner = strip_delimiters(quoted, '"', '"')?;
+
+    let mut text = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => text.push('\n'),
+            'x' => {
+                let code: String = chars.by_ref().take(2).collect();
+                text.push(u8::from_str_radix(&code, 16).ok()? as char);
+            }
+            escaped => text.push(escaped),
+        }
+    }
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod literal_tests {
+    use super::*;
+    use crate::codec::types::ValueBuilder;
+    use crate::encode::abi_encode;
+    use alloy_primitives::FixedBytes;
+
+    #[test]
+    fn test_literal_round_trip() {
+        let type_strs = [
+            "uint256", "int64", "address", "bool", "bytes", "bytes4", "string", "string",
+        ];
+        let values = ValueBuilder::new()
+            .add(U256::from(123))
+            .add_value(
+                Value::int_from_word("int64", I256::try_from(-5).unwrap().to_be_bytes()).unwrap(),
+            )
+            .add(Address::repeat_byte(0xab))
+            .add(false)
+            .add(Bytes::from(hex!("deadbeef")))
+            .add(FixedBytes::<4>::from(hex!("cafebabe")))
+            .add(String::from("say \"hi\",\n\u{1}"))
+            .add(String::from("café [x]"))
+            .build();
+
+        for (type_str, value) in type_strs.iter().zip(&values) {
+            let literal = value.to_solidity_literal(type_str).unwrap();
+            let parsed = Value::parse_solidity_literal(type_str, &literal).unwrap();
+            assert_eq!(parsed.to_solidity_literal(type_str).unwrap(), literal);
+            assert_eq!(
+                abi_encode(&[type_str], &[parsed]).unwrap(),
+                abi_encode(&[type_str], std::slice::from_ref(value)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_nested_literal_round_trip() {
+        let type_str = "(uint8[2][],string,address)";
+        let literal = "([[uint8(1), uint8(2)], [uint8(3), uint8(4)]], \"a, b\", address(0x0000000000000000000000000000000000000001))";
+
+        let value = Value::parse_solidity_literal(type_str, literal).unwrap();
+        assert_eq!(value.to_solidity_literal(type_str).unwrap(), literal);
+        assert!(value.matches_type(type_str));
+
+        // bare literals without casts
+        let value = Value::parse_solidity_literal("uint16[]", "[1, 2, 3]").unwrap();
+        assert_eq!(
+            value.to_solidity_literal("uint16[]").unwrap(),
+            "[uint16(1), uint16(2), uint16(3)]"
+        );
+        let Value::Collection(empty) = Value::parse_solidity_literal("uint256[]", "[]").unwrap()
+        else {
+            panic!("Expected array");
+        };
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_literal_shape_from_type() {
+        let owner = Address::repeat_byte(0x01);
+        let pair = Value::new(ValueBuilder::new().add(owner).add(owner).build());
+        let address = format!("address({})", owner.to_checksum(None));
+
+        assert_eq!(
+            pair.to_solidity_literal("(address,address)").unwrap(),
+            format!("({}, {})", address, address)
+        );
+        assert_eq!(
+            pair.to_solidity_literal("address[]").unwrap(),
+            format!("[{}, {}]", address, address)
+        );
+
+        let single = Value::new(ValueBuilder::new().add(U256::from(1)).build());
+        assert_eq!(
+            single.to_solidity_literal("uint256[]").unwrap(),
+            "[uint256(1)]"
+        );
+        assert_eq!(
+            single.to_solidity_literal("(uint256)").unwrap(),
+            "(uint256(1))"
+        );
+        assert_eq!(
+            Value::new(Vec::new())
+                .to_solidity_literal("uint256[]")
+                .unwrap(),
+            "[]"
+        );
+        for type_str in ["(address,address,address)", "address[3]", "address"] {
+            assert!(pair.to_solidity_literal(type_str).is_err());
+        }
+
+        // scalars are checked against their types too
+        let small = Value::uint_minimal(U256::from(7));
+        assert_eq!(small.to_solidity_literal("uint8").unwrap(), "uint8(7)");
+        assert_eq!(
+            small.to_solidity_literal("uint256"),
+            Err(CodecError::InvalidTypeAndValue(
+                "uint256".to_string(),
+                "7".to_string()
+            ))
+        );
+        assert!(pair.to_solidity_literal("(address,uint256)").is_err());
+    }
+
+    #[test]
+    fn test_invalid_literals() {
+        assert!(Value::parse_solidity_literal("uint256", "-1").is_err());
+        assert!(Value::parse_solidity_literal("uint8", "256").is_err());
+        assert!(Value::parse_solidity_literal("int8", "-129").is_err());
+        assert!(Value::parse_solidity_literal("bool", "yes").is_err());
+        assert!(Value::parse_solidity_literal("bytes4", "hex\"deadbeefaa\"").is_err());
+        assert!(Value::parse_solidity_literal("(uint256,bool)", "(1)").is_err());
+        assert!(Value::parse_solidity_literal("uint256[]", "1, 2").is_err());
+    }
+}
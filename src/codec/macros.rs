@@ -20,3 +20,37 @@ macro_rules! build_values {
         ]
     };
 }
+
+// Selector of a signature known at compile time, hashed on first use and
+// cached for every later call. Panics if the signature is malformed.
+#[macro_export]
+macro_rules! static_selector {
+    ($signature:expr) => {{
+        static SELECTOR: std::sync::OnceLock<[u8; 4]> = std::sync::OnceLock::new();
+        *SELECTOR.get_or_init(|| {
+            let selector = $crate::encode::abi_encode_selector($signature)
+                .expect("static_selector! requires a valid signature");
+            [selector[0], selector[1], selector[2], selector[3]]
+        })
+    }};
+}
+
+#[cfg(test)]
+mod macros_tests {
+    use crate::encode::abi_encode_selector;
+
+    fn transfer_selector() -> [u8; 4] {
+        static_selector!("transfer(address,uint256)")
+    }
+
+    #[test]
+    fn test_static_selector() {
+        let expected = abi_encode_selector("transfer(address,uint256)").unwrap();
+        assert_eq!(transfer_selector().to_vec(), expected);
+        assert_eq!(transfer_selector(), transfer_selector());
+        assert_eq!(
+            static_selector!("balanceOf(address) view returns (uint256)"),
+            [0x70, 0xa0, 0x82, 0x31]
+        );
+    }
+}
@@ -1,6 +1,9 @@
+pub mod config;
 pub mod extensions;
+pub mod fixed;
 pub mod implementations;
 pub mod initializer;
+pub mod literal;
 pub mod macros;
 pub mod traits;
 pub mod types;
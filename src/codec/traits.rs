@@ -1,3 +1,5 @@
+use crate::codec::types::Value;
+use crate::errors::CodecError;
 use std::any::Any;
 use std::fmt::Debug;
 
@@ -18,3 +20,13 @@ pub trait EncodeCodec: Any {
 pub trait DecodeCodec {
     fn from_bytes<const BYTES: usize>(bytes: [u8; BYTES]) -> Self;
 }
+
+// Conversion from a decoded value into a concrete Rust type
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, CodecError>;
+}
+
+// Conversion from a concrete Rust type into an encodable value
+pub trait ToValue {
+    fn to_value(&self) -> Value;
+}
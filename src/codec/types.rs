@@ -1,4 +1,14 @@
-use crate::codec::traits::BoxTrait;
+use crate::codec::traits::{BoxTrait, EncodeCodec};
+use crate::common::{
+    check_type_and_value, get_array_element_type, get_bytes_from_type, is_array, is_fixed_bytes,
+    is_tuple,
+};
+use crate::decode::{DecodeOptions, abi_decode, abi_decode_with_options};
+use crate::errors::CodecError;
+use alloy_primitives::aliases::{I256, U256};
+use alloy_primitives::{Address, Bytes, FixedBytes, hex};
+use std::any::Any;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum Value {
@@ -13,7 +23,7 @@ impl Value {
 
     pub fn get_i(&self, index: usize) -> &Self {
         match self {
-            Value::Single(_, _) => &self,
+            Value::Single(_, _) => self,
             Value::Collection(values) => &values[index],
         }
     }
@@ -21,6 +31,319 @@ impl Value {
     pub fn is_collection(&self) -> bool {
         matches!(self, Value::Collection(_))
     }
+
+    // an array whose elements all have one type, compared recursively with
+    // only the lengths of inner arrays free to differ; a bare scalar is not
+    // an array
+    pub fn is_homogeneous_array(&self) -> bool {
+        match self {
+            Value::Collection(values) => is_homogeneous(values),
+            Value::Single(_, _) => false,
+        }
+    }
+
+    // borrow the stored value without cloning it
+    pub fn as_bytes_ref(&self) -> Option<&Bytes> {
+        self.downcast_ref()
+    }
+
+    pub fn as_string_ref(&self) -> Option<&String> {
+        self.downcast_ref()
+    }
+
+    // any integer as a BigInt, reading signed types as two's complement
+    #[cfg(feature = "bigint")]
+    pub fn as_bigint(&self) -> Option<num_bigint::BigInt> {
+        let Value::Single(value, _) = self else {
+            return None;
+        };
+
+        let bytes = value.to_bytes_vec();
+        match value.eth_type() {
+            t if t.starts_with("uint") => Some(num_bigint::BigInt::from_bytes_be(
+                num_bigint::Sign::Plus,
+                &bytes,
+            )),
+            t if t.starts_with("int") => Some(num_bigint::BigInt::from_signed_bytes_be(&bytes)),
+            _ => None,
+        }
+    }
+
+    // Solidity source for the value, e.g. `uint256(123)` or `hex"dead"`. A
+    // collection is an array or a tuple only by the given type, as for
+    // `parse_solidity_literal`, and each scalar must match its type.
+    pub fn to_solidity_literal(&self, type_str: &str) -> Result<String, CodecError> {
+        let mismatch = || CodecError::InvalidTypeAndValue(type_str.to_string(), self.to_string());
+        let (value, type_str) = match self {
+            Value::Single(value, tag) => {
+                if is_array(type_str)?.0 || !check_type_and_value(type_str, self) {
+                    return Err(mismatch());
+                }
+                (value, tag.as_str())
+            }
+            Value::Collection(values) => {
+                let (is_array_type, size) = is_array(type_str)?;
+                let (member_types, delimiters) = if is_array_type {
+                    if size != 0 && size != values.len() {
+                        return Err(mismatch());
+                    }
+                    let element_type = get_array_element_type(type_str);
+                    (vec![element_type; values.len()], ("[", "]"))
+                } else {
+                    match is_tuple(type_str)? {
+                        (true, member_types) if member_types.len() == values.len() => {
+                            (member_types, ("(", ")"))
+                        }
+                        _ => return Err(mismatch()),
+                    }
+                };
+                let members = values
+                    .iter()
+                    .zip(member_types)
+                    .map(|(v, t)| v.to_solidity_literal(t))
+                    .collect::<Result<Vec<String>, CodecError>>()?;
+                return Ok(format!(
+                    "{}{}{}",
+                    delimiters.0,
+                    members.join(", "),
+                    delimiters.1
+                ));
+            }
+        };
+
+        Ok(match type_str {
+            t if t.starts_with("uint") || t.starts_with("int") => {
+                format!("{}({})", t, value.to_string())
+            }
+            "address" => match self.downcast_ref::<Address>() {
+                Some(address) => format!("address({})", address.to_checksum(None)),
+                None => format!("address({})", value.to_string()),
+            },
+            "bool" => value.to_string(),
+            "bytes" => format!("hex\"{}\"", hex::encode(value.to_bytes_vec())),
+            "string" => string_literal(&value.to_string()),
+            t if is_fixed_bytes(t) => {
+                let bytes = value.to_bytes_vec();
+                format!("bytes{}(hex\"{}\")", bytes.len(), hex::encode(bytes))
+            }
+            _ => value.to_string(),
+        })
+    }
+
+    // compares an integer value against a decimal string, e.g. "-5";
+    // anything that isn't an integer compares unequal
+    pub fn equals_decimal(&self, decimal: &str) -> bool {
+        let Value::Single(value, _) = self else {
+            return false;
+        };
+
+        let bytes = value.to_bytes_vec();
+        let value_type = value.eth_type();
+        if value_type.starts_with("uint") {
+            return U256::from_str_radix(decimal, 10)
+                .is_ok_and(|expected| U256::from_be_slice(&bytes) == expected);
+        }
+        if value_type.starts_with("int") && bytes.len() <= 32 {
+            let fill = if bytes.first().is_some_and(|b| b & 0x80 != 0) {
+                0xff
+            } else {
+                0x00
+            };
+            let mut word = [fill; 32];
+            word[32 - bytes.len()..].copy_from_slice(&bytes);
+            return I256::from_dec_str(decimal)
+                .is_ok_and(|expected| I256::from_be_bytes(word) == expected);
+        }
+
+        false
+    }
+
+    fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            Value::Single(value, _) => value.as_any().downcast_ref(),
+            Value::Collection(_) => None,
+        }
+    }
+
+    // builds an integer value from a raw two's-complement word, keeping the
+    // low width/8 bytes; the high bytes must be a consistent sign extension
+    pub fn int_from_word(type_str: &str, word: [u8; 32]) -> Result<Value, CodecError> {
+        if !(type_str.starts_with("uint") || type_str.starts_with("int")) {
+            return Err(CodecError::UnsupportedType(type_str.to_string()));
+        }
+
+        let options = DecodeOptions {
+            validate_padding: true,
+            ..Default::default()
+        };
+        let mut values = abi_decode_with_options(&[type_str], &word, &options).map_err(|e| {
+            if e == CodecError::DirtyPaddingBytes {
+                CodecError::InvalidTypeAndValue(
+                    type_str.to_string(),
+                    format!("0x{} is not sign-extended", hex::encode(word)),
+                )
+            } else {
+                e
+            }
+        })?;
+
+        Ok(values.remove(0))
+    }
+
+    // converts a scalar to another width of the same kind: integers keep
+    // their value and must fit the target, bytesN is zero-padded or cut on
+    // the right; anything else only casts to its own type
+    pub fn cast(&self, target_type: &str) -> Result<Value, CodecError> {
+        let source_type = self.eth_type();
+        let incompatible = || {
+            CodecError::InvalidTypeAndValue(
+                target_type.to_string(),
+                format!("{} {} cannot be cast", source_type, self.to_string()),
+            )
+        };
+        if self.is_collection() {
+            return Err(incompatible());
+        }
+        if source_type == target_type {
+            return Ok(self.clone());
+        }
+
+        let bytes = self.to_bytes_vec();
+        let integer_kind = |t: &str| ["uint", "int"].into_iter().find(|kind| t.starts_with(kind));
+        if let (Some(source_kind), Some(target_kind)) =
+            (integer_kind(&source_type), integer_kind(target_type))
+        {
+            if source_kind != target_kind || bytes.len() > 32 {
+                return Err(incompatible());
+            }
+            let negative = source_kind == "int" && bytes.first().is_some_and(|b| b & 0x80 != 0);
+            let mut word = [if negative { 0xff } else { 0x00 }; 32];
+            word[32 - bytes.len()..].copy_from_slice(&bytes);
+
+            return Value::int_from_word(target_type, word).map_err(|e| match e {
+                CodecError::InvalidTypeAndValue(_, _) => CodecError::InvalidTypeAndValue(
+                    target_type.to_string(),
+                    format!("{} does not fit", self.to_string()),
+                ),
+                e => e,
+            });
+        }
+
+        if is_fixed_bytes(&source_type)
+            && is_fixed_bytes(target_type)
+            && source_type != "function"
+            && target_type != "function"
+        {
+            let mut word = [0u8; 32];
+            let width = bytes.len().min(get_bytes_from_type(target_type));
+            word[..width].copy_from_slice(&bytes[..width]);
+
+            return Ok(abi_decode(&[target_type], &word)?.remove(0));
+        }
+
+        Err(incompatible())
+    }
+
+    // index paths where two values differ, with the string form of each
+    // side; a shape mismatch is reported once at the path where it occurs
+    pub fn diff(&self, other: &Value) -> Vec<(Vec<usize>, String, String)> {
+        let mut differences = Vec::new();
+        self.diff_at(other, &mut Vec::new(), &mut differences);
+        differences
+    }
+
+    fn diff_at(
+        &self,
+        other: &Value,
+        path: &mut Vec<usize>,
+        differences: &mut Vec<(Vec<usize>, String, String)>,
+    ) {
+        match (self, other) {
+            (Value::Collection(left), Value::Collection(right)) if left.len() == right.len() => {
+                for (i, (l, r)) in left.iter().zip(right).enumerate() {
+                    path.push(i);
+                    l.diff_at(r, path, differences);
+                    path.pop();
+                }
+            }
+            (Value::Single(_, _), Value::Single(_, _))
+                if self.eth_type() == other.eth_type()
+                    && self.to_bytes_vec() == other.to_bytes_vec() => {}
+            _ => differences.push((path.clone(), self.to_string(), other.to_string())),
+        }
+    }
+
+    // wraps every scalar in an Arc so that later clones only bump a reference
+    // count instead of deep-cloning each boxed value
+    pub fn shared(&self) -> Value {
+        match self {
+            Value::Single(value, type_str) => {
+                let any: &dyn Any = value.as_ref();
+                if any.is::<SharedValue>() {
+                    return self.clone();
+                }
+                let shared = SharedValue(Arc::from(value.clone_box()));
+                Value::Single(Box::new(shared), type_str.clone())
+            }
+            Value::Collection(values) => {
+                Value::Collection(values.iter().map(Value::shared).collect())
+            }
+        }
+    }
+
+    // collects values into an array, requiring every element to share one type
+    pub fn array_of<I: IntoIterator<Item = Value>>(iter: I) -> Result<Value, CodecError> {
+        let values: Vec<Value> = iter.into_iter().collect();
+        if let Some(first) = values.first() {
+            let element_type = first.eth_type();
+            if let Some(other) = values.iter().find(|v| v.eth_type() != element_type) {
+                return Err(CodecError::InvalidArray(format!(
+                    "mixed element types: {} and {}",
+                    element_type,
+                    other.eth_type()
+                )));
+            }
+        }
+
+        Ok(Value::Collection(values))
+    }
+
+    // stores the value as the narrowest uintN (N a multiple of 8) that holds
+    // it, so it reads back as that width, e.g. `U16::from_value`
+    pub fn uint_minimal(value: U256) -> Value {
+        let type_str = format!("uint{}", value.byte_len().max(1) * 8);
+        Value::int_from_word(&type_str, value.to_be_bytes()).expect("value fits its own width")
+    }
+
+    // recursively checks the value's shape against a type string: array
+    // lengths and element types, tuple arity and members, scalar tags and widths
+    pub fn matches_type(&self, type_str: &str) -> bool {
+        if let Ok((true, size)) = is_array(type_str) {
+            let element_type = get_array_element_type(type_str);
+            return match self {
+                Value::Collection(values) => {
+                    (size == 0 || values.len() == size)
+                        && values.iter().all(|v| v.matches_type(element_type))
+                }
+                Value::Single(_, _) => false,
+            };
+        }
+
+        match is_tuple(type_str) {
+            Ok((true, member_types)) => match self {
+                Value::Collection(values) => {
+                    values.len() == member_types.len()
+                        && values
+                            .iter()
+                            .zip(member_types)
+                            .all(|(v, t)| v.matches_type(t))
+                }
+                Value::Single(_, _) => false,
+            },
+            Ok((false, _)) => !self.is_collection() && check_type_and_value(type_str, self),
+            Err(_) => false,
+        }
+    }
 }
 
 impl Clone for Value {
@@ -32,10 +355,21 @@ impl Clone for Value {
     }
 }
 
+// Reference-counted scalar produced by `Value::shared`. It is transparent to
+// downcasting: `as_any` exposes the wrapped value, not the wrapper.
+#[derive(Debug, Clone)]
+pub struct SharedValue(pub(crate) Arc<dyn BoxTrait>);
+
 pub struct ValueBuilder {
     values: Vec<Value>,
 }
 
+impl Default for ValueBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ValueBuilder {
     pub fn new() -> Self {
         Self { values: Vec::new() }
@@ -97,6 +431,23 @@ impl ValueBuilder {
         self
     }
 
+    // like `add_array` for borrowed elements; each one is tagged with the
+    // given element type, so an empty slice still makes a typed array. As
+    // with `add`, a tag that doesn't fit the elements fails at encode time.
+    pub fn add_array_slice<T: BoxTrait + Clone>(
+        &mut self,
+        values: &[T],
+        type_str: &str,
+    ) -> &mut Self {
+        let inner_values = values
+            .iter()
+            .map(|v| create_value(v.clone(), type_str))
+            .collect();
+
+        self.values.push(Value::Collection(inner_values));
+        self
+    }
+
     pub fn add_tuple(&mut self, values: Vec<Box<dyn BoxTrait>>) -> &mut Self {
         let mut inner_values = Vec::new();
         for value in values {
@@ -121,9 +472,134 @@ impl ValueBuilder {
         self
     }
 
+    pub fn add_function(&mut self, address: Address, selector: [u8; 4]) -> &mut Self {
+        let mut function = [0u8; 24];
+        function[..20].copy_from_slice(address.as_slice());
+        function[20..].copy_from_slice(&selector);
+        self.values
+            .push(create_value(FixedBytes::<24>::from(function), "function"));
+        self
+    }
+
+    // adds a dynamic array whose element count must fall within `min..=max`
+    pub fn add_array_bounded<T: BoxTrait + 'static>(
+        &mut self,
+        values: Vec<T>,
+        min: usize,
+        max: usize,
+    ) -> Result<&mut Self, CodecError> {
+        if !(min..=max).contains(&values.len()) {
+            return Err(CodecError::InvalidTypeAndValue(
+                "array".to_string(),
+                format!("{} elements, expected {} to {}", values.len(), min, max),
+            ));
+        }
+
+        Ok(self.add_array(values))
+    }
+
+    // numeric booleans: only 0 and 1 are accepted
+    pub fn add_bool_from_int(&mut self, value: u8) -> Result<&mut Self, CodecError> {
+        match value {
+            0 | 1 => Ok(self.add(value == 1)),
+            _ => Err(CodecError::InvalidTypeAndValue(
+                "bool".to_string(),
+                value.to_string(),
+            )),
+        }
+    }
+
+    pub fn add_value(&mut self, value: Value) -> &mut Self {
+        self.values.push(value);
+        self
+    }
+
     pub fn build(&self) -> Vec<Value> {
         self.values.clone()
     }
+
+    // owned counterparts of the `add*` methods, for one-shot construction
+    // ending in `finish`
+
+    pub fn with<T: BoxTrait + 'static>(mut self, value: T) -> Self {
+        self.add(value);
+        self
+    }
+
+    pub fn with_array<T: BoxTrait + 'static>(mut self, values: Vec<T>) -> Self {
+        self.add_array(values);
+        self
+    }
+
+    pub fn with_tuple(mut self, values: Vec<Box<dyn BoxTrait>>) -> Self {
+        self.add_tuple(values);
+        self
+    }
+
+    pub fn with_function(mut self, address: Address, selector: [u8; 4]) -> Self {
+        self.add_function(address, selector);
+        self
+    }
+
+    pub fn with_value(mut self, value: Value) -> Self {
+        self.add_value(value);
+        self
+    }
+
+    pub fn finish(self) -> Vec<Value> {
+        self.values
+    }
+}
+
+// quotes a string for Solidity source; non-ASCII text needs `unicode"..."`
+fn string_literal(text: &str) -> String {
+    let mut literal = if text.is_ascii() {
+        String::from("\"")
+    } else {
+        String::from("unicode\"")
+    };
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            c if c.is_ascii_control() => literal.push_str(&format!("\\x{:02x}", c as u8)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+
+    literal
+}
+
+// Nested collections are not compared by eth_type, which joins their members:
+// inner arrays of different lengths are still one type.
+pub(crate) fn is_homogeneous(values: &[Value]) -> bool {
+    let Some(first) = values.first() else {
+        return true;
+    };
+
+    values.iter().all(|value| same_type(first, value))
+}
+
+// Collections match member by member, as tuples or equal-length arrays do,
+// or as two arrays whose elements share one type whatever their lengths.
+fn same_type(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Single(_, a_type), Value::Single(_, b_type)) => a_type == b_type,
+        (Value::Collection(a_values), Value::Collection(b_values)) => {
+            let members_match = a_values.len() == b_values.len()
+                && a_values.iter().zip(b_values).all(|(a, b)| same_type(a, b));
+            members_match
+                || (is_homogeneous(a_values)
+                    && is_homogeneous(b_values)
+                    && match (a_values.first(), b_values.first()) {
+                        (Some(a), Some(b)) => same_type(a, b),
+                        _ => true,
+                    })
+        }
+        _ => false,
+    }
 }
 
 pub fn create_value<T: BoxTrait + 'static>(value: T, type_str: &str) -> Value {
@@ -138,3 +614,601 @@ pub fn create_array_value<T: BoxTrait + 'static>(values: Vec<T>, element_type: &
             .collect(),
     )
 }
+
+// Each element is stored as the alloy type of the tag's width, so the
+// result matches `uintN[]`. Panics if the tag isn't a valid `uintN` or a
+// value doesn't fit it; `try_uint_array` reports that as an error instead.
+pub fn uint_array(type_str: &str, vals: Vec<U256>) -> Value {
+    try_uint_array(type_str, vals).expect("values fit the uintN tag")
+}
+
+pub fn int_array(type_str: &str, vals: Vec<I256>) -> Value {
+    try_int_array(type_str, vals).expect("values fit the intN tag")
+}
+
+pub fn try_uint_array(type_str: &str, vals: Vec<U256>) -> Result<Value, CodecError> {
+    sized_int_array("uint", type_str, vals.into_iter().map(|v| v.to_be_bytes()))
+}
+
+pub fn try_int_array(type_str: &str, vals: Vec<I256>) -> Result<Value, CodecError> {
+    sized_int_array("int", type_str, vals.into_iter().map(|v| v.to_be_bytes()))
+}
+
+fn sized_int_array(
+    kind: &str,
+    type_str: &str,
+    words: impl Iterator<Item = [u8; 32]>,
+) -> Result<Value, CodecError> {
+    let is_sized = type_str
+        .strip_prefix(kind)
+        .is_some_and(|bits| !bits.is_empty() && get_bytes_from_type(type_str) != 0);
+    if !is_sized {
+        return Err(CodecError::UnsupportedType(type_str.to_string()));
+    }
+
+    words
+        .map(|word| Value::int_from_word(type_str, word))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Value::Collection)
+}
+
+pub fn address_array(vals: Vec<Address>) -> Value {
+    create_array_value(vals, "address")
+}
+
+pub fn bool_array(vals: Vec<bool>) -> Value {
+    create_array_value(vals, "bool")
+}
+
+pub fn bytes32_array(vals: Vec<FixedBytes<32>>) -> Value {
+    create_array_value(vals, "bytes32")
+}
+
+pub fn bytes_array(vals: Vec<Bytes>) -> Value {
+    create_array_value(vals, "bytes")
+}
+
+pub fn string_array(vals: Vec<String>) -> Value {
+    create_array_value(vals, "string")
+}
+
+#[cfg(test)]
+mod types_tests {
+    use super::*;
+    use crate::codec::traits::FromValue;
+    use crate::codec::utils::get_collection_i;
+    use crate::encode::{abi_encode, abi_encode_packed};
+    use alloy_primitives::aliases::{I16, U8, U16};
+
+    #[test]
+    fn test_owned_chaining() {
+        let owned = ValueBuilder::default()
+            .with(U256::from(1))
+            .with_array(vec![Address::ZERO])
+            .with_value(create_value(String::from("x"), "string"))
+            .finish();
+        let borrowed = ValueBuilder::new()
+            .add(U256::from(1))
+            .add_array(vec![Address::ZERO])
+            .add(String::from("x"))
+            .build();
+
+        let type_strs = ["uint256", "address[]", "string"];
+        assert_eq!(
+            abi_encode(&type_strs, &owned).unwrap(),
+            abi_encode(&type_strs, &borrowed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_int_from_word() {
+        let mut word = [0xff; 32];
+        word[31] = 0xd6;
+        let value = Value::int_from_word("int128", word).unwrap();
+        assert_eq!(value.eth_type(), "int128");
+        assert_eq!(value.to_string(), "-42");
+
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&1_000_000u64.to_be_bytes());
+        let value = Value::int_from_word("uint64", word).unwrap();
+        assert_eq!(value.eth_type(), "uint64");
+        assert_eq!(value.to_string(), "1000000");
+
+        word[0] = 0x01;
+        assert!(matches!(
+            Value::int_from_word("uint64", word),
+            Err(CodecError::InvalidTypeAndValue(_, _))
+        ));
+        assert_eq!(
+            Value::int_from_word("address", word).unwrap_err(),
+            CodecError::UnsupportedType("address".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cast() {
+        let values = ValueBuilder::new()
+            .add(U8::from(5))
+            .add(U256::from(300))
+            .add(I16::try_from(-2).unwrap())
+            .add(FixedBytes::<4>::from([0xde, 0xad, 0xbe, 0xef]))
+            .build();
+
+        let widened = values[0].cast("uint256").unwrap();
+        assert_eq!(widened.eth_type(), "uint256");
+        assert_eq!(U256::from_value(&widened).unwrap(), U256::from(5));
+        assert_eq!(
+            values[1].cast("uint8").unwrap_err(),
+            CodecError::InvalidTypeAndValue("uint8".to_string(), "300 does not fit".to_string())
+        );
+        assert_eq!(values[1].cast("uint16").unwrap().to_string(), "300");
+
+        let narrowed = values[2].cast("int8").unwrap();
+        assert_eq!(narrowed.eth_type(), "int8");
+        assert_eq!(narrowed.to_string(), "-2");
+        assert!(values[2].cast("uint16").is_err());
+
+        let bytes = values[3].cast("bytes2").unwrap();
+        assert_eq!(bytes.to_string(), "dead");
+        assert_eq!(
+            values[3].cast("bytes8").unwrap().to_string(),
+            "deadbeef00000000"
+        );
+
+        assert_eq!(
+            values[0].cast("address").unwrap_err(),
+            CodecError::InvalidTypeAndValue(
+                "address".to_string(),
+                "uint8 5 cannot be cast".to_string()
+            )
+        );
+        assert!(values[3].cast("uint32").is_err());
+        assert!(Value::new(values.clone()).cast("uint256").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let tuple = |amount: u64, tag: &str| {
+            Value::new(
+                ValueBuilder::new()
+                    .add(Address::repeat_byte(0x01))
+                    .add_value(Value::new(
+                        ValueBuilder::new()
+                            .add(U256::from(amount))
+                            .add(String::from(tag))
+                            .build(),
+                    ))
+                    .build(),
+            )
+        };
+
+        assert!(tuple(1, "a").diff(&tuple(1, "a")).is_empty());
+        assert_eq!(
+            tuple(1, "a").diff(&tuple(2, "a")),
+            vec![(vec![1, 0], "1".to_string(), "2".to_string())]
+        );
+
+        let shorter = Value::new(ValueBuilder::new().add(Address::repeat_byte(0x01)).build());
+        let differences = tuple(1, "a").diff(&shorter);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].0, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_shared() {
+        let values = ValueBuilder::new()
+            .add(U256::from(42))
+            .add_array(vec![String::from("a"), String::from("b")])
+            .build();
+        let shared = Value::new(values.clone()).shared();
+        let cloned = shared.clone();
+
+        assert_eq!(cloned.to_string(), Value::new(values.clone()).to_string());
+        assert_eq!(
+            abi_encode(
+                &["uint256", "string[]"],
+                &get_collection_i(std::slice::from_ref(&cloned), 0)
+            )
+            .unwrap(),
+            abi_encode(&["uint256", "string[]"], &values).unwrap()
+        );
+        assert_eq!(U256::from_value(shared.get_i(0)).unwrap(), U256::from(42));
+
+        // clones point at the same allocation instead of copying it
+        let inner = |v: &Value| match v.get_i(1).get_i(0) {
+            Value::Single(value, _) => value.as_any() as *const dyn Any as *const u8,
+            Value::Collection(_) => unreachable!(),
+        };
+        assert_eq!(inner(&shared), inner(&cloned));
+        assert!(std::ptr::eq(inner(&shared.shared()), inner(&shared)));
+    }
+
+    #[test]
+    fn test_array_of() {
+        let candidates = [0x00u8, 0x11, 0x00, 0x22];
+        let array = Value::array_of(
+            candidates
+                .iter()
+                .filter(|b| **b != 0)
+                .map(|b| create_value(Address::repeat_byte(*b), "address")),
+        )
+        .unwrap();
+
+        let expected = ValueBuilder::new()
+            .add_array(vec![Address::repeat_byte(0x11), Address::repeat_byte(0x22)])
+            .build();
+        assert_eq!(
+            abi_encode(&["address[]"], &[array]).unwrap(),
+            abi_encode(&["address[]"], &expected).unwrap()
+        );
+
+        let mixed = Value::array_of([
+            create_value(Address::ZERO, "address"),
+            create_value(U256::from(1), "uint256"),
+        ]);
+        assert_eq!(
+            mixed.unwrap_err(),
+            CodecError::InvalidArray("mixed element types: address and uint256".to_string())
+        );
+    }
+
+    #[test]
+    fn test_typed_arrays() {
+        let numbers = vec![U256::from(1), U256::from(2), U256::MAX];
+        let from_builder = ValueBuilder::new().add_array(numbers.clone()).build();
+        assert_eq!(
+            abi_encode(&["uint256[]"], &[uint_array("uint256", numbers)]).unwrap(),
+            abi_encode(&["uint256[]"], &from_builder).unwrap()
+        );
+
+        let small = uint_array("uint8", vec![U256::from(1), U256::from(255)]);
+        assert!(small.matches_type("uint8[]"));
+        assert_eq!(U8::from_value(small.get_i(1)).unwrap(), U8::from(255));
+        assert!(try_uint_array("uint8", vec![U256::from(256)]).is_err());
+        let negative = int_array("int16", vec![I256::MINUS_ONE]);
+        assert!(negative.matches_type("int16[]"));
+        assert_eq!(negative.get_i(0).to_string(), "-1");
+        for tag in ["address", "int256", "uint", "uint7"] {
+            assert_eq!(
+                try_uint_array(tag, vec![U256::from(1)]).err(),
+                Some(CodecError::UnsupportedType(tag.to_string()))
+            );
+        }
+        assert!(try_int_array("uint256", vec![]).is_err());
+
+        let addresses = vec![Address::repeat_byte(0xaa), Address::repeat_byte(0xbb)];
+        let from_builder = ValueBuilder::new().add_array(addresses.clone()).build();
+        let value = address_array(addresses);
+        assert!(value.matches_type("address[2]"));
+        assert_eq!(
+            abi_encode(&["address[]"], &[value]).unwrap(),
+            abi_encode(&["address[]"], &from_builder).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_matches_type() {
+        let tuple = ValueBuilder::new()
+            .add_value(Value::new(
+                ValueBuilder::new()
+                    .add(U256::from(1))
+                    .add(String::from("one"))
+                    .add_array(vec![Address::ZERO, Address::repeat_byte(0x01)])
+                    .build(),
+            ))
+            .build();
+
+        assert!(tuple[0].matches_type("(uint256,string,address[2])"));
+        assert!(tuple[0].matches_type("(uint256,string,address[])"));
+        assert!(!tuple[0].matches_type("(uint256,string,address[3])"));
+        assert!(!tuple[0].matches_type("(uint128,string,address[])"));
+        assert!(!tuple[0].matches_type("(uint256,string)"));
+        assert!(!tuple[0].matches_type("uint256"));
+    }
+
+    #[test]
+    fn test_add_value() {
+        let inner_types = vec!["string", "uint256"];
+        let inner_values = ValueBuilder::new()
+            .add(String::from("Hello, world!"))
+            .add(U256::from(7))
+            .build();
+        let decoded = abi_decode(
+            &inner_types,
+            &abi_encode(&inner_types, &inner_values).unwrap(),
+        )
+        .unwrap();
+
+        let type_strs = vec!["(string,uint256)", "uint256"];
+        let values = ValueBuilder::new()
+            .add_value(Value::new(decoded))
+            .add(U256::from(1))
+            .build();
+        let expected = ValueBuilder::new()
+            .add_tuple(vec![
+                Box::new(String::from("Hello, world!")) as Box<dyn BoxTrait>,
+                Box::new(U256::from(7)) as Box<dyn BoxTrait>,
+            ])
+            .add(U256::from(1))
+            .build();
+
+        assert!(values[0].is_collection());
+        assert_eq!(
+            abi_encode(&type_strs, &values).unwrap(),
+            abi_encode(&type_strs, &expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_function() {
+        let address = Address::repeat_byte(0x11);
+        let selector = [0xa9, 0x05, 0x9c, 0xbb];
+        let type_strs = vec!["function"];
+        let values = ValueBuilder::new().add_function(address, selector).build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(encoded.len(), 32);
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        let Value::Single(function, type_str) = &decoded[0] else {
+            panic!("Expected single value");
+        };
+        let function = function.as_any().downcast_ref::<FixedBytes<24>>().unwrap();
+        assert_eq!(type_str, "function");
+        assert_eq!(&function[..20], address.as_slice());
+        assert_eq!(&function[20..], &selector);
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_add_bool_from_int() {
+        let mut builder = ValueBuilder::new();
+        builder
+            .add_bool_from_int(0)
+            .unwrap()
+            .add_bool_from_int(1)
+            .unwrap();
+        let values = builder.build();
+        assert!(!bool::from_value(&values[0]).unwrap());
+        assert!(bool::from_value(&values[1]).unwrap());
+        assert_eq!(values[1].eth_type(), "bool");
+
+        assert_eq!(
+            ValueBuilder::new().add_bool_from_int(2).err(),
+            Some(CodecError::InvalidTypeAndValue(
+                "bool".to_string(),
+                "2".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_add_array_bounded() {
+        let mut builder = ValueBuilder::new();
+        builder
+            .add_array_bounded(vec![U256::from(1)], 1, 3)
+            .unwrap()
+            .add_array_bounded(vec![U256::from(1), U256::from(2), U256::from(3)], 1, 3)
+            .unwrap();
+        let values = builder.build();
+        assert_eq!(
+            abi_encode(&["uint256[]", "uint256[]"], &values).unwrap(),
+            abi_encode(
+                &["uint256[]", "uint256[]"],
+                &ValueBuilder::new()
+                    .add_array(vec![U256::from(1)])
+                    .add_array(vec![U256::from(1), U256::from(2), U256::from(3)])
+                    .build()
+            )
+            .unwrap()
+        );
+
+        assert_eq!(
+            ValueBuilder::new()
+                .add_array_bounded(vec![U256::from(1); 4], 1, 3)
+                .err(),
+            Some(CodecError::InvalidTypeAndValue(
+                "array".to_string(),
+                "4 elements, expected 1 to 3".to_string()
+            ))
+        );
+        assert!(
+            ValueBuilder::new()
+                .add_array_bounded(Vec::<U256>::new(), 1, 3)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_uint_minimal() {
+        assert_eq!(Value::uint_minimal(U256::ZERO).eth_type(), "uint8");
+        assert_eq!(Value::uint_minimal(U256::from(255)).eth_type(), "uint8");
+        assert_eq!(Value::uint_minimal(U256::from(256)).eth_type(), "uint16");
+        assert_eq!(Value::uint_minimal(U256::MAX).eth_type(), "uint256");
+
+        let value = Value::uint_minimal(U256::from(256));
+        assert_eq!(
+            abi_encode_packed(&["uint16"], std::slice::from_ref(&value)).unwrap(),
+            vec![0x01, 0x00]
+        );
+        assert_eq!(U16::from_value(&value).unwrap(), U16::from(256));
+        assert!(U256::from_value(&value).is_err());
+        assert_eq!(
+            U8::from_value(&Value::uint_minimal(U256::from(7))).unwrap(),
+            U8::from(7)
+        );
+    }
+
+    #[test]
+    fn test_as_bytes_and_string_ref() {
+        let values = ValueBuilder::new()
+            .add(Bytes::from(vec![0x5a; 1024]))
+            .add(String::from("borrowed"))
+            .build();
+
+        let bytes = values[0].as_bytes_ref().unwrap();
+        assert_eq!(bytes.len(), 1024);
+        let Value::Single(stored, _) = &values[0] else {
+            panic!("Expected single value");
+        };
+        assert!(std::ptr::eq(
+            bytes,
+            stored.as_any().downcast_ref::<Bytes>().unwrap()
+        ));
+        assert_eq!(values[1].as_string_ref().unwrap(), "borrowed");
+
+        assert!(values[0].as_string_ref().is_none());
+        assert!(values[1].as_bytes_ref().is_none());
+        assert!(Value::new(values.clone()).as_bytes_ref().is_none());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_as_bigint() {
+        use alloy_primitives::aliases::U8;
+        use num_bigint::BigInt;
+
+        let encoded = I256::try_from(-1234567).unwrap().to_be_bytes::<32>();
+        let decoded = abi_decode(&["int64", "uint256"], &[encoded, [0xff; 32]].concat()).unwrap();
+        assert_eq!(decoded[0].as_bigint(), Some(BigInt::from(-1234567)));
+        assert_eq!(decoded[1].as_bigint(), Some((BigInt::from(1) << 256) - 1));
+
+        let values = ValueBuilder::new()
+            .add(U8::from(200))
+            .add(String::from("200"))
+            .build();
+        assert_eq!(values[0].as_bigint(), Some(BigInt::from(200)));
+        assert_eq!(values[1].as_bigint(), None);
+    }
+
+    #[test]
+    fn test_to_solidity_literal() {
+        let values = ValueBuilder::new()
+            .add(U256::from(123))
+            .add(I256::try_from(-5).unwrap())
+            .add(Address::repeat_byte(0xab))
+            .add(true)
+            .add(Bytes::from(hex!("deadbeef")))
+            .add(FixedBytes::<4>::from(hex!("cafebabe")))
+            .add(String::from("say \"hi\"\n"))
+            .add(String::from("café"))
+            .build();
+        let literals: Vec<String> = values
+            .iter()
+            .map(|v| v.to_solidity_literal(&v.eth_type()).unwrap())
+            .collect();
+        assert_eq!(
+            literals,
+            vec![
+                "uint256(123)",
+                "int256(-5)",
+                "address(0xABaBaBaBABabABabAbAbABAbABabababaBaBABaB)",
+                "true",
+                "hex\"deadbeef\"",
+                "bytes4(hex\"cafebabe\")",
+                "\"say \\\"hi\\\"\\n\"",
+                "unicode\"café\"",
+            ]
+        );
+
+        let nested = Value::new(vec![
+            uint_array("uint8", vec![U256::from(1), U256::from(2)]),
+            uint_array("uint8", vec![U256::from(3), U256::from(4)]),
+        ]);
+        assert_eq!(
+            nested.to_solidity_literal("uint8[][]").unwrap(),
+            "[[uint8(1), uint8(2)], [uint8(3), uint8(4)]]"
+        );
+
+        let tuple = Value::new(
+            ValueBuilder::new()
+                .add(U256::from(1))
+                .add(String::from("x"))
+                .build(),
+        );
+        assert_eq!(
+            tuple.to_solidity_literal("(uint256,string)").unwrap(),
+            "(uint256(1), \"x\")"
+        );
+    }
+
+    #[test]
+    fn test_equals_decimal() {
+        let encoded = [
+            U256::from(1000).to_be_bytes::<32>(),
+            I256::try_from(-5).unwrap().to_be_bytes::<32>(),
+        ]
+        .concat();
+        let decoded = abi_decode(&["uint256", "int64"], &encoded).unwrap();
+        assert!(decoded[0].equals_decimal("1000"));
+        assert!(!decoded[0].equals_decimal("1001"));
+        assert!(!decoded[0].equals_decimal("-1000"));
+        assert!(decoded[1].equals_decimal("-5"));
+        assert!(!decoded[1].equals_decimal("5"));
+
+        let values = ValueBuilder::new().add(String::from("1000")).build();
+        assert!(!values[0].equals_decimal("1000"));
+        assert!(!Value::new(decoded).equals_decimal("1000"));
+    }
+
+    #[test]
+    fn test_is_homogeneous_array() {
+        let values = ValueBuilder::new()
+            .add_array(vec![U256::from(1), U256::from(2)])
+            .add(U256::from(1))
+            .build();
+        assert!(values[0].is_homogeneous_array());
+        assert!(!values[1].is_homogeneous_array());
+        assert!(Value::new(Vec::new()).is_homogeneous_array());
+
+        let mixed = Value::new(vec![values[1].clone(), create_value(true, "bool")]);
+        assert!(!mixed.is_homogeneous_array());
+        let mixed = Value::new(vec![values[1].clone(), values[0].clone()]);
+        assert!(!mixed.is_homogeneous_array());
+
+        // nested members are compared too, only inner array lengths may differ
+        let pair = |a: Value, b: Value| Value::new(vec![a, b]);
+        let number = || create_value(U256::from(1), "uint256");
+        let flag = || create_value(true, "bool");
+        let swapped = pair(pair(number(), flag()), pair(flag(), number()));
+        assert!(!swapped.is_homogeneous_array());
+        assert!(abi_encode(&["(uint256,bool)[]"], std::slice::from_ref(&swapped)).is_err());
+        let tuples = pair(pair(number(), flag()), pair(number(), flag()));
+        assert!(tuples.is_homogeneous_array());
+        let ragged = pair(Value::new(vec![number()]), pair(number(), number()));
+        assert!(ragged.is_homogeneous_array());
+        let differently_typed = pair(Value::new(vec![number()]), Value::new(vec![flag()]));
+        assert!(!differently_typed.is_homogeneous_array());
+        assert!(pair(Value::new(Vec::new()), Value::new(vec![flag()])).is_homogeneous_array());
+    }
+
+    #[test]
+    fn test_add_array_slice() {
+        let addresses = [Address::repeat_byte(0x01), Address::repeat_byte(0x02)];
+        let values = ValueBuilder::new()
+            .add_array_slice(&addresses, "address")
+            .add_array_slice::<Address>(&[], "address")
+            .build();
+
+        assert!(values[0].matches_type("address[]"));
+        let owned = ValueBuilder::new().add_array(addresses.to_vec()).build();
+        assert_eq!(
+            abi_encode(&["address[]"], &values[..1]).unwrap(),
+            abi_encode(&["address[]"], &owned).unwrap()
+        );
+
+        assert!(values[1].matches_type("address[]"));
+        let encoded = abi_encode(&["address[]"], &values[1..]).unwrap();
+        let decoded = abi_decode(&["address[]"], &encoded).unwrap();
+        assert!(Vec::<Address>::from_value(&decoded[0]).unwrap().is_empty());
+
+        // a tag that doesn't describe the elements is caught when encoding
+        let mistagged = ValueBuilder::new()
+            .add_array_slice(&addresses, "uint256")
+            .add_array_slice(&[U256::from(256)], "uint8")
+            .build();
+        assert!(!mistagged[0].matches_type("uint256[]"));
+        assert!(abi_encode(&["uint256[]"], &mistagged[..1]).is_err());
+        assert!(abi_encode(&["uint8[]"], &mistagged[1..]).is_err());
+    }
+}
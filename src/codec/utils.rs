@@ -1,4 +1,6 @@
+use crate::codec::traits::{BoxTrait, EncodeCodec};
 use crate::codec::types::Value;
+use crate::errors::CodecError;
 
 pub fn pad_left(input: Vec<u8>, target_length: usize) -> Vec<u8> {
     if input.len() >= target_length {
@@ -21,9 +23,24 @@ pub fn pad_right(input: Vec<u8>, target_length: usize) -> Vec<u8> {
     padded
 }
 
-pub fn get_collection_i(values: &Vec<Value>, index: usize) -> Vec<Value> {
+pub fn get_collection_i(values: &[Value], index: usize) -> Vec<Value> {
     match &values[index] {
         Value::Single(_, _) => vec![values[index].clone()],
         Value::Collection(vals) => vals.to_vec(),
     }
 }
+
+pub fn downcast_single<T: Clone + 'static>(value: &Value) -> Result<T, CodecError> {
+    let downcasted = match value {
+        Value::Single(inner, _) => inner.as_any().downcast_ref::<T>().cloned(),
+        Value::Collection(_) => None,
+    };
+
+    downcasted.ok_or_else(|| {
+        CodecError::InvalidTypeAndValue(std::any::type_name::<T>().to_string(), value.to_string())
+    })
+}
+
+pub fn to_single<T: BoxTrait + Clone>(value: &T) -> Value {
+    Value::Single(Box::new(value.clone()), value.eth_type())
+}
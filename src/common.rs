@@ -1,41 +1,195 @@
 use crate::codec::traits::EncodeCodec;
 use crate::errors::CodecError;
 
+// Structural check: `bytes`, `string` and `T[]` are dynamic, as is any
+// fixed-size array or tuple containing one. A comma-separated list is
+// dynamic if any of its members is.
 pub fn is_dynamic(t: &str) -> bool {
-    t.contains("[]") || t.contains("bytes") || t.contains("string")
+    let t = t.trim();
+    let members = split_top_level(t);
+    if members.len() > 1 {
+        return members.iter().any(|m| is_dynamic(m));
+    }
+
+    if let (Some(inner), Some(open)) = (t.strip_suffix(']'), t.rfind('[')) {
+        return open + 1 == inner.len() || is_dynamic(&t[..open]);
+    }
+
+    if let Some(inner) = t.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        return split_top_level(inner).iter().any(|m| is_dynamic(m));
+    }
+
+    t == "bytes" || t == "string"
 }
 
-pub fn is_array(t: &str) -> Result<(bool, usize), CodecError> {
-    let count_open_brackets = t.chars().filter(|c| *c == '[').count();
-    if count_open_brackets != t.chars().filter(|c| *c == ']').count() {
-        return Err(CodecError::InvalidArray(t.to_string()));
+fn split_top_level(t: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in t.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&t[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&t[start..]);
+
+    parts
+}
+
+// `bytes1` through `bytes32`, plus `function`, which is encoded as `bytes24`
+pub fn is_fixed_bytes(t: &str) -> bool {
+    t == "function"
+        || t.strip_prefix("bytes")
+            .and_then(|size| size.parse::<usize>().ok())
+            .is_some_and(|size| (1..=32).contains(&size) && !t.starts_with("bytes0"))
+}
+
+// `fixedMxN` and `ufixedMxN` as (signed, M, N), where M is a multiple of 8
+// up to 256 and N at most 80; bare `fixed`/`ufixed` mean `fixed128x18`
+pub fn parse_fixed_type(t: &str) -> Option<(bool, usize, usize)> {
+    let (signed, rest) = match t.strip_prefix('u') {
+        Some(rest) => (false, rest),
+        None => (true, t),
+    };
+    let rest = rest.strip_prefix("fixed")?;
+    if rest.is_empty() {
+        return Some((signed, 128, 18));
     }
 
-    if count_open_brackets > 0 {
-        let open_brackets_index = t.rfind('[').map_or(-1, |i| i as isize);
-        let close_brackets_index = t.rfind(']').map_or(-1, |i| i as isize);
-        let close_parenthesis_index = t.rfind(')').map_or(-1, |i| i as isize);
+    let (bits, decimals) = rest.split_once('x')?;
+    let number = |digits: &str| {
+        (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| digits.parse::<usize>().ok())
+            .flatten()
+    };
+    let (bits, decimals) = (number(bits)?, number(decimals)?);
+
+    ((8..=256).contains(&bits) && bits % 8 == 0 && decimals <= 80)
+        .then_some((signed, bits, decimals))
+}
+
+// signed values are sign-extended to the full word instead of zero-padded
+pub fn is_signed(t: &str) -> bool {
+    t.starts_with("int") || t.starts_with("fixed")
+}
 
-        if open_brackets_index < close_parenthesis_index
-            || close_brackets_index < close_parenthesis_index
-        {
-            return Ok((false, 0));
+// Checks that every `(` is closed by a `)` and every `[` by a `]`, in order,
+// so malformed types are rejected before the per-kind parsers see them.
+pub fn validate_type_syntax(t: &str) -> Result<(), CodecError> {
+    let invalid = |c: char| match c {
+        '[' | ']' => CodecError::InvalidArray(t.to_string()),
+        _ => CodecError::InvalidTuple(t.to_string()),
+    };
+
+    let mut open = Vec::new();
+    for c in t.chars() {
+        match c {
+            '(' | '[' => open.push(c),
+            ')' | ']' => {
+                let expected = if c == ')' { '(' } else { '[' };
+                if open.pop() != Some(expected) {
+                    return Err(invalid(c));
+                }
+            }
+            _ => {}
         }
+    }
+
+    match open.pop() {
+        Some(c) => Err(invalid(c)),
+        None => Ok(()),
+    }
+}
+
+// deepest tuple/array nesting the codecs will follow before giving up
+pub const DEFAULT_MAX_DEPTH: usize = 32;
 
-        let mut array_size: usize = 0;
-        if close_brackets_index > open_brackets_index + 1 {
-            array_size = match t[(open_brackets_index + 1) as usize..close_brackets_index as usize]
-                .parse()
-            {
-                Ok(size) => size,
-                Err(_) => return Err(CodecError::InvalidArray(t.to_string())),
-            };
+// Nesting depth of a type: elementary types are 0, and every tuple or array
+// dimension around them adds one. Computed without recursion so adversarial
+// type strings cannot exhaust the stack.
+pub fn type_depth(t: &str) -> usize {
+    // (deepest finished member, depth of the member being read) per open tuple
+    let mut levels: Vec<(usize, usize)> = vec![(0, 0)];
+    for c in t.chars() {
+        match c {
+            '(' => levels.push((0, 0)),
+            ')' if levels.len() > 1 => {
+                let (deepest, current) = levels.pop().unwrap();
+                levels.last_mut().unwrap().1 = deepest.max(current) + 1;
+            }
+            '[' => levels.last_mut().unwrap().1 += 1,
+            ',' => {
+                let level = levels.last_mut().unwrap();
+                *level = (level.0.max(level.1), 0);
+            }
+            _ => {}
         }
+    }
+
+    levels
+        .into_iter()
+        .map(|(deepest, current)| deepest.max(current))
+        .max()
+        .unwrap_or(0)
+}
 
-        return Ok((true, array_size));
+pub fn check_depth(type_strs: &[&str], max_depth: usize) -> Result<(), CodecError> {
+    match type_strs.iter().map(|t| type_depth(t)).max() {
+        Some(depth) if depth > max_depth => Err(CodecError::RecursionLimitExceeded(depth)),
+        _ => Ok(()),
+    }
+}
+
+pub fn get_head_size(t: &str) -> Result<usize, CodecError> {
+    if is_dynamic(t) {
+        return Ok(32);
+    }
+
+    let (is_array_type, size) = is_array(t)?;
+    if is_array_type {
+        return Ok(size * get_head_size(get_array_element_type(t))?);
+    }
+
+    let (is_tuple_type, tuple_types) = is_tuple(t)?;
+    if is_tuple_type {
+        return tuple_types.iter().map(|t| get_head_size(t)).sum();
     }
 
-    Ok((false, 0))
+    Ok(32)
+}
+
+// Only a trailing `[..]` makes the type itself an array: `(uint256)[]` is an
+// array of tuples, while `(uint256[])` is a tuple holding one.
+pub fn is_array(t: &str) -> Result<(bool, usize), CodecError> {
+    let count_open_brackets = t.chars().filter(|c| *c == '[').count();
+    if count_open_brackets != t.chars().filter(|c| *c == ']').count() {
+        return Err(CodecError::InvalidArray(t.to_string()));
+    }
+
+    let Some(inner) = t.strip_suffix(']') else {
+        return Ok((false, 0));
+    };
+    let invalid = || CodecError::InvalidArray(t.to_string());
+    let open_bracket_index = inner.rfind('[').ok_or_else(invalid)?;
+    let size = &inner[open_bracket_index + 1..];
+    if size.is_empty() {
+        return Ok((true, 0));
+    }
+
+    size.parse().map(|size| (true, size)).map_err(|_| invalid())
+}
+
+pub fn get_array_element_type(t: &str) -> &str {
+    match t.rfind('[') {
+        Some(index) => &t[..index],
+        None => t,
+    }
 }
 
 pub fn is_tuple(t: &str) -> Result<(bool, Vec<&str>), CodecError> {
@@ -47,7 +201,7 @@ pub fn is_tuple(t: &str) -> Result<(bool, Vec<&str>), CodecError> {
 
     if count_open_parenthesis > 0 {
         let parameter_types =
-            split_parameter_types(&t[t.find('(').unwrap() + 1..t.rfind(')').unwrap()]);
+            split_parameter_types(&t[t.find('(').unwrap()..=t.rfind(')').unwrap()]);
         return Ok((true, parameter_types));
     }
 
@@ -59,12 +213,115 @@ pub fn get_parameter_types(t: &str) -> Result<Vec<&str>, CodecError> {
         return Err(CodecError::InvalidFunctionSignature(t.to_string()));
     }
 
-    let parameter_types =
-        split_parameter_types(&t[t.find('(').unwrap() + 1..t.rfind(')').unwrap()]);
+    let t = function_head(t)?;
+    let parameter_types = split_parameter_types(&t[t.find('(').unwrap()..=t.rfind(')').unwrap()]);
 
     Ok(parameter_types)
 }
 
+// the `name(inputs)` part of a function definition, without a leading
+// `function` keyword, modifiers or a `returns (...)` clause
+pub fn function_head(def: &str) -> Result<&str, CodecError> {
+    Ok(split_function_definition(def)?.0)
+}
+
+pub fn parse_function_io(def: &str) -> Result<(String, Vec<&str>, Vec<&str>), CodecError> {
+    let (head, _) = split_function_definition(def)?;
+    let name = head[..head.find('(').unwrap()].trim().to_string();
+    let inputs = get_parameter_types(head)?
+        .into_iter()
+        .map(|p| split_parameter_name(p).0)
+        .collect();
+    let outputs = parse_named_outputs(def)?
+        .into_iter()
+        .map(|(t, _)| t)
+        .collect();
+
+    Ok((name, inputs, outputs))
+}
+
+// output types of a definition's `returns (...)` clause with their names, if any
+pub fn parse_named_outputs(def: &str) -> Result<Vec<(&str, Option<&str>)>, CodecError> {
+    let (_, rest) = split_function_definition(def)?;
+    let outputs = match rest.find("returns") {
+        Some(index) => {
+            let (returns, _) = split_function_definition(&rest[index + "returns".len()..])?;
+            split_parameter_types(returns.trim())
+        }
+        None => Vec::new(),
+    };
+
+    Ok(outputs.into_iter().map(split_parameter_name).collect())
+}
+
+// splits `uint112 reserve0` into its type and name, dropping a data location
+// such as `memory`; nested tuple member names are left untouched
+pub fn split_parameter_name(param: &str) -> (&str, Option<&str>) {
+    let param = param.trim();
+    let split = param
+        .rfind(char::is_whitespace)
+        .filter(|index| !param[*index..].contains(')'));
+    let Some(index) = split else {
+        return (param, None);
+    };
+
+    let (type_str, name) = (param[..index].trim_end(), &param[index + 1..]);
+    if is_data_location(name) {
+        return (type_str, None);
+    }
+    match type_str.rsplit_once(char::is_whitespace) {
+        Some((inner, location)) if is_data_location(location) => (inner.trim_end(), Some(name)),
+        _ => (type_str, Some(name)),
+    }
+}
+
+fn is_data_location(word: &str) -> bool {
+    matches!(word, "memory" | "calldata" | "storage" | "indexed")
+}
+
+fn split_function_definition(def: &str) -> Result<(&str, &str), CodecError> {
+    let trimmed = def.trim();
+    let trimmed = trimmed
+        .strip_prefix("function ")
+        .unwrap_or(trimmed)
+        .trim_start();
+    let invalid = || CodecError::InvalidFunctionSignature(def.to_string());
+
+    let open = trimmed.find('(').ok_or_else(invalid)?;
+    let mut depth = 0;
+    for (i, c) in trimmed[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => continue,
+        }
+        if depth == 0 {
+            return Ok(trimmed.split_at(open + i + 1));
+        }
+    }
+
+    Err(invalid())
+}
+
+pub fn is_packed_safe(signature: &str) -> Result<bool, CodecError> {
+    for t in get_parameter_types(signature)? {
+        let (is_tuple_type, _) = is_tuple(t)?;
+        if is_tuple_type {
+            return Ok(false);
+        }
+
+        let (is_array_type, _) = is_array(t)?;
+        if is_array_type {
+            let element_type = get_array_element_type(t);
+            if is_array(element_type)?.0 || is_dynamic(element_type) {
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
+
 pub fn split_parameter_types(t: &str) -> Vec<&str> {
     let mut result = Vec::new();
     let mut start = 0;
@@ -102,7 +359,40 @@ pub fn split_parameter_types(t: &str) -> Vec<&str> {
     result
 }
 
+pub fn normalize_type_case(t: &str) -> String {
+    t.split_inclusive(|c: char| !c.is_ascii_alphanumeric())
+        .map(|part| {
+            let word_end = part
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(part.len());
+            let (word, rest) = part.split_at(word_end);
+            let lowercase_word = word.to_ascii_lowercase();
+            if is_type_keyword(&lowercase_word) {
+                lowercase_word + rest
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+fn is_type_keyword(word: &str) -> bool {
+    [
+        "uint", "int", "address", "bool", "bytes", "string", "function",
+    ]
+    .iter()
+    .any(|keyword| {
+        word.strip_prefix(keyword)
+            .is_some_and(|size| size.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
 pub fn check_type_and_value<T: EncodeCodec>(t: &str, v: &T) -> bool {
+    // `fixed` and `fixed128x18` name the same type
+    if let (Some(t_fixed), Some(v_fixed)) = (parse_fixed_type(t), parse_fixed_type(&v.eth_type())) {
+        return t_fixed == v_fixed && v.bytes_length() == get_bytes_from_type(t);
+    }
+
     if t == v.eth_type() {
         if t == "bytes" || t == "string" {
             return true;
@@ -139,7 +429,7 @@ pub fn get_bytes_from_type(type_str: &str) -> usize {
         "uint168" | "int168" | "bytes21" => 21,
         "uint176" | "int176" | "bytes22" => 22,
         "uint184" | "int184" | "bytes23" => 23,
-        "uint192" | "int192" | "bytes24" => 24,
+        "uint192" | "int192" | "bytes24" | "function" => 24,
         "uint200" | "int200" | "bytes25" => 25,
         "uint208" | "int208" | "bytes26" => 26,
         "uint216" | "int216" | "bytes27" => 27,
@@ -149,7 +439,7 @@ pub fn get_bytes_from_type(type_str: &str) -> usize {
         "uint248" | "int248" | "bytes31" => 31,
         "uint256" | "int256" | "bytes32" => 32,
         "bytes" | "string" => u64::MAX as usize,
-        _ => 0,
+        _ => parse_fixed_type(type_str).map_or(0, |(_, bits, _)| bits / 8),
     }
 }
 
@@ -188,6 +478,22 @@ mod common_tests {
         );
     }
 
+    #[test]
+    fn get_parameter_types_single_tuple() {
+        assert_eq!(
+            get_parameter_types("f((address,address))").unwrap(),
+            vec!["(address,address)"]
+        );
+        assert_eq!(
+            get_parameter_types("f((uint256),(bool))").unwrap(),
+            vec!["(uint256)", "(bool)"]
+        );
+        assert_eq!(
+            is_tuple("((address,address))").unwrap(),
+            (true, vec!["(address,address)"])
+        );
+    }
+
     #[test]
     fn get_parameter_types_success_1() {
         let signature = "blabla(uint256,address,(uint256[],bytes)[],address,uint8)";
@@ -250,34 +556,141 @@ mod common_tests {
         );
     }
 
+    #[test]
+    fn parse_function_io_1() {
+        let result =
+            parse_function_io("function balanceOf(address) external view returns (uint256)");
+        assert_eq!(
+            result,
+            Ok(("balanceOf".to_string(), vec!["address"], vec!["uint256"]))
+        );
+        assert_eq!(
+            function_head("balanceOf(address) external view returns (uint256)"),
+            Ok("balanceOf(address)")
+        );
+    }
+
+    #[test]
+    fn parse_named_outputs_1() {
+        let result = parse_named_outputs(
+            "function getReserves() view returns (uint112 reserve0, uint112 _reserve1, uint32)",
+        );
+        assert_eq!(
+            result,
+            Ok(vec![
+                ("uint112", Some("reserve0")),
+                ("uint112", Some("_reserve1")),
+                ("uint32", None)
+            ])
+        );
+
+        assert_eq!(
+            split_parameter_name("string memory name"),
+            ("string", Some("name"))
+        );
+        assert_eq!(split_parameter_name("bytes calldata"), ("bytes", None));
+        assert_eq!(
+            split_parameter_name("(uint256 a, bool b)[] items"),
+            ("(uint256 a, bool b)[]", Some("items"))
+        );
+        assert_eq!(
+            split_parameter_name("(uint256 a, bool b)"),
+            ("(uint256 a, bool b)", None)
+        );
+    }
+
+    #[test]
+    fn parse_function_io_2() {
+        let result = parse_function_io("swap((address,uint256)[],bytes)");
+        assert_eq!(
+            result,
+            Ok((
+                "swap".to_string(),
+                vec!["(address,uint256)[]", "bytes"],
+                vec![]
+            ))
+        );
+
+        let result = parse_function_io("getReserves()returns(uint112,uint112,uint32)");
+        assert_eq!(
+            result,
+            Ok((
+                "getReserves".to_string(),
+                vec![],
+                vec!["uint112", "uint112", "uint32"]
+            ))
+        );
+        assert!(parse_function_io("transfer").is_err());
+    }
+
+    #[test]
+    fn parse_fixed_type_1() {
+        assert_eq!(parse_fixed_type("ufixed128x18"), Some((false, 128, 18)));
+        assert_eq!(parse_fixed_type("fixed"), Some((true, 128, 18)));
+        assert_eq!(parse_fixed_type("ufixed"), Some((false, 128, 18)));
+        assert_eq!(parse_fixed_type("fixed8x0"), Some((true, 8, 0)));
+        assert_eq!(parse_fixed_type("fixed256x80"), Some((true, 256, 80)));
+        assert_eq!(parse_fixed_type("fixed12x2"), None);
+        assert_eq!(parse_fixed_type("fixed128x81"), None);
+        assert_eq!(parse_fixed_type("fixed128"), None);
+        assert_eq!(parse_fixed_type("ufixedx18"), None);
+        assert_eq!(parse_fixed_type("uint128"), None);
+        assert_eq!(get_bytes_from_type("ufixed"), 16);
+        assert!(is_signed("fixed64x10") && !is_signed("ufixed"));
+    }
+
+    #[test]
+    fn is_fixed_bytes_1() {
+        assert!(is_fixed_bytes("bytes1"));
+        assert!(is_fixed_bytes("bytes32"));
+        assert!(is_fixed_bytes("function"));
+        assert!(!is_fixed_bytes("bytes"));
+        assert!(!is_fixed_bytes("bytes0"));
+        assert!(!is_fixed_bytes("bytes04"));
+        assert!(!is_fixed_bytes("bytes33"));
+        assert!(!is_fixed_bytes("uint32"));
+    }
+
+    #[test]
+    fn is_dynamic_tuple_arrays() {
+        assert!(is_dynamic("(uint256,address)[]"));
+        assert!(is_dynamic("(uint256,bytes)[3]"));
+        assert!(!is_dynamic("(uint256,address)[3]"));
+        assert!(!is_dynamic("(bytes32,address)[3]"));
+        assert!(!is_dynamic("bytes32"));
+        assert!(!is_dynamic("(bytes4,(bytes32,uint8)[2])"));
+        assert!(is_dynamic("(bytes4,(bytes32,string)[2])"));
+        assert!(is_dynamic("bytes32[]"));
+    }
+
     #[test]
     fn is_dynamic_1() {
         let result = is_dynamic("address,uint256[]");
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
     fn is_dynamic_2() {
         let result = is_dynamic("uint256,bytes");
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
     fn is_dynamic_3() {
         let result = is_dynamic("address,string");
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
     fn is_dynamic_4() {
         let result = is_dynamic("address[3]");
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
     fn is_dynamic_5() {
         let result = is_dynamic("address,bytes[3],uint256");
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -307,6 +720,17 @@ mod common_tests {
         );
     }
 
+    #[test]
+    fn is_array_tuples() {
+        assert_eq!(is_array("(uint256[])"), Ok((false, 0)));
+        assert_eq!(is_array("(uint256)[]"), Ok((true, 0)));
+        assert_eq!(is_array("(uint256[])[]"), Ok((true, 0)));
+        assert_eq!(is_array("(uint256[2])[3]"), Ok((true, 3)));
+        assert_eq!(is_array("((uint256)[],bool[2])"), Ok((false, 0)));
+        assert_eq!(get_array_element_type("(uint256[])[]"), "(uint256[])");
+        assert!(is_array("(uint256)[x]").is_err());
+    }
+
     #[test]
     fn is_tuple_success_1() {
         let result = is_tuple("(uint256,address,(uint256[],bytes)[],address,uint8)");
@@ -335,4 +759,92 @@ mod common_tests {
             ))
         );
     }
+
+    #[test]
+    fn get_head_size_1() {
+        assert_eq!(get_head_size("uint256"), Ok(32));
+        assert_eq!(get_head_size("uint256[]"), Ok(32));
+        assert_eq!(get_head_size("(uint256,address)"), Ok(64));
+        assert_eq!(get_head_size("(uint256,address)[2]"), Ok(128));
+        assert_eq!(get_head_size("(uint256,string)[2]"), Ok(32));
+    }
+
+    #[test]
+    fn normalize_type_case_1() {
+        assert_eq!(normalize_type_case("UINT256"), "uint256");
+        assert_eq!(normalize_type_case("Address[3]"), "address[3]");
+        assert_eq!(
+            normalize_type_case("(Bool,Bytes32,STRING[])[]"),
+            "(bool,bytes32,string[])[]"
+        );
+        assert_eq!(normalize_type_case("MyType"), "MyType");
+    }
+
+    #[test]
+    fn is_packed_safe_1() {
+        assert_eq!(is_packed_safe("foo(uint256,address)"), Ok(true));
+        assert_eq!(is_packed_safe("foo(bytes,string,uint8[3])"), Ok(true));
+    }
+
+    #[test]
+    fn is_packed_safe_2() {
+        assert_eq!(is_packed_safe("foo(uint256[][])"), Ok(false));
+        assert_eq!(is_packed_safe("foo(string[])"), Ok(false));
+        assert_eq!(is_packed_safe("foo(uint256,(address,uint256))"), Ok(false));
+    }
+
+    #[test]
+    fn type_depth_1() {
+        assert_eq!(type_depth("uint256"), 0);
+        assert_eq!(type_depth("uint256[2][]"), 2);
+        assert_eq!(type_depth("(uint256,(bool,address[]))"), 3);
+        assert_eq!(type_depth("((uint256)[],bytes)[3]"), 4);
+        assert_eq!(type_depth("(uint8[][],bool)"), 3);
+    }
+
+    #[test]
+    fn validate_type_syntax_1() {
+        assert_eq!(
+            validate_type_syntax("uint256[)]"),
+            Err(CodecError::InvalidTuple("uint256[)]".to_string()))
+        );
+        assert_eq!(
+            validate_type_syntax("(uint256])"),
+            Err(CodecError::InvalidArray("(uint256])".to_string()))
+        );
+        assert!(validate_type_syntax("(uint256[2]").is_err());
+        assert!(validate_type_syntax("uint256]").is_err());
+
+        for t in [
+            "uint256",
+            "uint256[2][]",
+            "(uint256,(bool,bytes)[])[3]",
+            "()",
+        ] {
+            assert_eq!(validate_type_syntax(t), Ok(()));
+        }
+    }
+
+    #[test]
+    fn keyword_function_names() {
+        for name in [
+            "tuple", "array", "function", "bytes32", "uint256", "returns",
+        ] {
+            let signature = format!("{}(uint256,(bool,address)[])", name);
+            assert_eq!(
+                get_parameter_types(&signature).unwrap(),
+                vec!["uint256", "(bool,address)[]"]
+            );
+
+            let definition = format!("function {}(uint256 a) external returns (bool)", name);
+            let (parsed_name, inputs, outputs) = parse_function_io(&definition).unwrap();
+            assert_eq!(parsed_name, name);
+            assert_eq!(inputs, vec!["uint256"]);
+            assert_eq!(outputs, vec!["bool"]);
+            assert_eq!(
+                function_head(&definition).unwrap(),
+                format!("{}(uint256 a)", name)
+            );
+        }
+    }
 }
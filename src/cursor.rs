@@ -0,0 +1,163 @@
+use crate::errors::CodecError;
+use alloy_primitives::aliases::U256;
+use std::cell::Cell;
+
+// A read position over an encoded buffer. Every read is bounds-checked so
+// that malformed input surfaces as an error instead of a slice panic.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> DecodeCursor<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    pub fn read_word(&mut self) -> Result<[u8; 32], CodecError> {
+        let mut word = [0u8; 32];
+        word.copy_from_slice(self.read_bytes(32)?);
+
+        Ok(word)
+    }
+
+    // reads a length or offset word as a usize
+    pub fn read_offset(&mut self) -> Result<usize, CodecError> {
+        read_usize(&self.read_word()?)
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], CodecError> {
+        let end = self.checked_end(length)?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    pub fn skip(&mut self, length: usize) -> Result<(), CodecError> {
+        self.position = self.checked_end(length)?;
+
+        Ok(())
+    }
+
+    // everything from the current position onwards
+    pub fn rest(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+
+    // everything from an absolute offset onwards, as pointed to by a head word
+    pub fn tail(&self, offset: usize) -> Result<&'a [u8], CodecError> {
+        if offset > self.data.len() {
+            return Err(CodecError::OutOfBounds(offset, self.data.len()));
+        }
+
+        Ok(&self.data[offset..])
+    }
+
+    fn checked_end(&self, length: usize) -> Result<usize, CodecError> {
+        match self.position.checked_add(length) {
+            Some(end) if end <= self.data.len() => Ok(end),
+            _ => Err(CodecError::OutOfBounds(
+                self.position.saturating_add(length),
+                self.data.len(),
+            )),
+        }
+    }
+}
+
+// words a decode may consume per word of input
+const WORK_FACTOR: usize = 8;
+
+// Caps the words one decode consumes. A canonical encoding is read about once
+// over, but offsets may point many heads at the same tail; the budget keeps
+// such revisits from growing the work faster than the input.
+pub(crate) struct WorkBudget {
+    limit: usize,
+    used: Cell<usize>,
+}
+
+impl WorkBudget {
+    pub(crate) fn for_input(data: &[u8]) -> Self {
+        Self {
+            limit: (data.len() / 32 + 1).saturating_mul(WORK_FACTOR),
+            used: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn charge(&self, words: usize) -> Result<(), CodecError> {
+        let used = self.used.get().saturating_add(words);
+        if used > self.limit {
+            return Err(CodecError::WorkLimitExceeded(self.limit));
+        }
+        self.used.set(used);
+
+        Ok(())
+    }
+}
+
+// Lengths and offsets are 32-byte words; anything that doesn't fit the
+// target's usize (e.g. above u32::MAX on wasm32) is rejected, not truncated.
+pub(crate) fn read_usize(word: &[u8]) -> Result<usize, CodecError> {
+    let value = U256::from_be_slice(word);
+    usize::try_from(value).map_err(|_| CodecError::LengthOverflow(value))
+}
+
+#[cfg(test)]
+mod cursor_tests {
+    use super::*;
+
+    #[test]
+    fn test_read_word() {
+        let mut data = [0u8; 40];
+        data[31] = 7;
+        let mut cursor = DecodeCursor::new(&data);
+
+        assert_eq!(cursor.read_word().unwrap()[31], 7);
+        assert_eq!(cursor.position(), 32);
+        assert_eq!(cursor.remaining(), 8);
+        assert_eq!(cursor.read_word(), Err(CodecError::OutOfBounds(64, 40)));
+        // a failed read leaves the cursor where it was
+        assert_eq!(cursor.position(), 32);
+        assert_eq!(cursor.read_bytes(8).unwrap(), &[0u8; 8]);
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn test_read_offset() {
+        let mut data = [0u8; 64];
+        data[31] = 0x20;
+        data[32..].fill(0xff);
+        let mut cursor = DecodeCursor::new(&data);
+
+        let offset = cursor.read_offset().unwrap();
+        assert_eq!(offset, 0x20);
+        assert_eq!(cursor.tail(offset).unwrap().len(), 32);
+        assert_eq!(
+            cursor.read_offset(),
+            Err(CodecError::LengthOverflow(U256::MAX))
+        );
+        assert_eq!(cursor.tail(65), Err(CodecError::OutOfBounds(65, 64)));
+    }
+
+    #[test]
+    fn test_skip() {
+        let data = [0u8; 32];
+        let mut cursor = DecodeCursor::new(&data);
+
+        assert_eq!(
+            cursor.skip(usize::MAX),
+            Err(CodecError::OutOfBounds(usize::MAX, 32))
+        );
+        cursor.skip(32).unwrap();
+        assert!(cursor.rest().is_empty());
+    }
+}
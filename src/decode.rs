@@ -1,58 +1,530 @@
-use crate::codec::traits::DecodeCodec;
+use crate::codec::config::Codec;
+use crate::codec::fixed::FixedPoint;
+use crate::codec::traits::{BoxTrait, DecodeCodec, FromValue};
 use crate::codec::types::Value;
-use crate::common::{get_bytes_from_type, is_array, is_dynamic, is_tuple, split_parameter_types};
-use crate::encode::abi_encode_selector;
+use crate::common::{
+    DEFAULT_MAX_DEPTH, check_depth, get_array_element_type, get_bytes_from_type, get_head_size,
+    get_parameter_types, is_array, is_dynamic, is_fixed_bytes, is_signed, is_tuple,
+    normalize_type_case, parse_named_outputs, validate_type_syntax,
+};
+use crate::cursor::{DecodeCursor, WorkBudget};
+use crate::encode::{abi_encode, event_topic, try_abi_encode_selector_array};
 use crate::errors::CodecError;
-use alloy_primitives::{Address, Bytes, FixedBytes, aliases::*};
+use alloy_primitives::{Address, Bytes, FixedBytes, aliases::*, hex};
 
 pub fn abi_decode_with_signature(
     signature: &str,
-    encoded_values: &Vec<u8>,
+    encoded_values: &[u8],
 ) -> Result<Vec<Value>, CodecError> {
-    let selector = abi_encode_selector(signature)?;
-    let type_strs = split_parameter_types(signature);
-    if selector != encoded_values[..4] {
-        return Err(CodecError::InvalidSelector);
+    Codec::default().decode_with_signature(signature, encoded_values)
+}
+
+// decodes `data` with or without the leading selector; arguments always span
+// whole words, so a prefixed buffer is recognised by its selector and a
+// length that is four bytes past a word boundary
+pub fn abi_decode_auto(signature: &str, data: &[u8]) -> Result<Vec<Value>, CodecError> {
+    let selector = try_abi_encode_selector_array(signature)?;
+    let type_strs = get_parameter_types(signature)?;
+
+    let has_selector = data.len() % 32 == 4 && data.starts_with(&selector);
+    let encoded_values = if has_selector { &data[4..] } else { data };
+
+    abi_decode(&type_strs, encoded_values)
+}
+
+// decodes return data against the named outputs of a full definition;
+// unnamed outputs are keyed by their position
+pub fn abi_decode_returns_named(
+    full_def: &str,
+    data: &[u8],
+) -> Result<Vec<(String, Value)>, CodecError> {
+    let outputs = parse_named_outputs(full_def)?;
+    let type_strs: Vec<&str> = outputs.iter().map(|(t, _)| *t).collect();
+    let values = abi_decode(&type_strs, data)?;
+
+    Ok(outputs
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, ((_, name), value))| (name.map_or_else(|| i.to_string(), String::from), value))
+        .collect())
+}
+
+// Decodes calldata for a definition with named parameters, such as
+// `transfer(address to, uint256 amount)`, keyed in declaration order.
+// Unnamed parameters are keyed by position and repeated names get a `_<position>`
+// suffix; a suffixed key that clashes with another parameter's name is an error
+// rather than overwriting it.
+#[cfg(feature = "indexmap")]
+pub fn abi_decode_named_map(
+    signature: &str,
+    data: &[u8],
+) -> Result<indexmap::IndexMap<String, Value>, CodecError> {
+    use crate::common::{function_head, parse_function_io, split_parameter_name};
+
+    let (name, type_strs, _) = parse_function_io(signature)?;
+    let names: Vec<Option<&str>> = get_parameter_types(function_head(signature)?)?
+        .into_iter()
+        .map(|param| split_parameter_name(param).1)
+        .collect();
+    let canonical = format!("{}({})", name, type_strs.join(","));
+    let values = abi_decode_with_signature(&canonical, data)?;
+
+    let mut map = indexmap::IndexMap::with_capacity(values.len());
+    for (i, (name, value)) in names.into_iter().zip(values).enumerate() {
+        let key = match name {
+            Some(name) if !map.contains_key(name) => name.to_string(),
+            Some(name) => format!("{}_{}", name, i),
+            None => i.to_string(),
+        };
+        if map.contains_key(&key) {
+            return Err(CodecError::InvalidFunctionSignature(format!(
+                "{}: duplicate parameter name {}",
+                signature, key
+            )));
+        }
+        map.insert(key, value);
+    }
+
+    Ok(map)
+}
+
+// decodes each payload on its own so one malformed entry doesn't abort the
+// rest; payloads may or may not carry the selector, as in `abi_decode_auto`
+pub fn abi_decode_batch(
+    signature: &str,
+    payloads: &[Vec<u8>],
+) -> Vec<Result<Vec<Value>, CodecError>> {
+    payloads
+        .iter()
+        .map(|payload| abi_decode_auto(signature, payload))
+        .collect()
+}
+
+// picks the candidate signature that explains the calldata: its selector
+// must match and a strict decode must re-encode to exactly the same bytes,
+// which settles selector collisions
+pub fn best_decode(calldata: &[u8], candidates: &[&str]) -> Option<(String, Vec<Value>)> {
+    candidates.iter().find_map(|signature| {
+        let values = decode_exact(signature, calldata)?;
+        Some((signature.to_string(), values))
+    })
+}
+
+// Proxies forward calldata unchanged, so a call to a proxy decodes against
+// the implementation's signatures. Matching is by selector (then an exact
+// re-encode, as in `best_decode`); the proxy's own functions are not known.
+pub fn decode_through_proxy(
+    calldata: &[u8],
+    impl_signatures: &[&str],
+) -> Option<(String, Vec<Value>)> {
+    best_decode(calldata, impl_signatures)
+}
+
+// whether the calldata is exactly a canonical encoding of a call to the
+// signature: matching selector, strict decode and no trailing bytes
+pub fn is_valid_calldata(signature: &str, calldata: &[u8]) -> bool {
+    decode_exact(signature, calldata).is_some()
+}
+
+fn decode_exact(signature: &str, calldata: &[u8]) -> Option<Vec<Value>> {
+    let (selector, encoded_values) = calldata.split_at_checked(4)?;
+    if try_abi_encode_selector_array(signature).ok()? != selector {
+        return None;
+    }
+
+    let type_strs = get_parameter_types(signature).ok()?;
+    let values =
+        abi_decode_with_options(&type_strs, encoded_values, &DecodeOptions::strict()).ok()?;
+    if abi_encode(&type_strs, &values).ok()? != encoded_values {
+        return None;
+    }
+
+    Some(values)
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodeOptions {
+    // reject dynamic tails that overlap or leave gaps between each other
+    pub validate_offsets: bool,
+    // upper bound on the number of elements any array may declare
+    pub max_array_len: Option<usize>,
+    // require padding bytes to be zero (sign-extended for negative ints), per spec
+    pub validate_padding: bool,
+    // deepest tuple/array nesting accepted in a type string
+    pub max_depth: usize,
+    // return the raw word, tagged "raw", for static types the crate cannot decode
+    pub raw_on_unsupported: bool,
+    // decode `bytes`/`string` cut short by the end of the data up to the
+    // bytes available instead of failing
+    pub truncated_dynamic_ok: bool,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            validate_offsets: false,
+            max_array_len: None,
+            validate_padding: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            raw_on_unsupported: false,
+            truncated_dynamic_ok: false,
+        }
+    }
+}
+
+impl DecodeOptions {
+    pub fn strict() -> Self {
+        Self {
+            validate_offsets: true,
+            validate_padding: true,
+            ..Default::default()
+        }
     }
+}
+
+pub fn abi_decode(type_strs: &[&str], encoded_values: &[u8]) -> Result<Vec<Value>, CodecError> {
+    abi_decode_with_options(type_strs, encoded_values, &DecodeOptions::default())
+}
+
+// for type lists built at runtime as owned strings
+pub fn abi_decode_owned(type_strs: &[String], data: &[u8]) -> Result<Vec<Value>, CodecError> {
+    let type_strs: Vec<&str> = type_strs.iter().map(|t| t.as_str()).collect();
+
+    abi_decode(&type_strs, data)
+}
 
-    let encoded_values = &encoded_values[4..];
+pub fn abi_decode_with_options(
+    type_strs: &[&str],
+    encoded_values: &[u8],
+    options: &DecodeOptions,
+) -> Result<Vec<Value>, CodecError> {
+    for type_str in type_strs {
+        validate_type_syntax(type_str)?;
+    }
+    check_depth(type_strs, options.max_depth)?;
 
-    abi_decode(&type_strs, &encoded_values.to_vec())
+    decode_params(
+        type_strs,
+        encoded_values,
+        options,
+        &WorkBudget::for_input(encoded_values),
+    )
 }
 
-pub fn abi_decode(
-    type_strs: &Vec<&str>,
-    encoded_values: &Vec<u8>,
+fn decode_params(
+    type_strs: &[&str],
+    encoded_values: &[u8],
+    options: &DecodeOptions,
+    work: &WorkBudget,
 ) -> Result<Vec<Value>, CodecError> {
-    let mut cursor = 0;
-    let mut values = Vec::new();
+    let mut cursor = DecodeCursor::new(encoded_values);
+    let mut values = Vec::with_capacity(type_strs.len());
+    let mut tail_regions = Vec::new();
+
+    let head_end = if options.validate_offsets {
+        Some(
+            type_strs
+                .iter()
+                .map(|t| get_head_size(t))
+                .sum::<Result<usize, _>>()?,
+        )
+    } else {
+        None
+    };
 
     for type_str in type_strs {
-        let (is_array_type, size) = is_array(type_str)?;
         let is_dynamic_type = is_dynamic(type_str);
-        let (is_tuple_type, tuple_types) = is_tuple(type_str)?;
-
-        let encoded_value = handle_offset(encoded_values, cursor, is_dynamic_type, 0);
-        let (value, size) = if is_array_type {
-            let array_values = decode_array(
-                type_str,
-                encoded_value,
-                size,
-                is_dynamic_type,
-                is_tuple_type,
-                &tuple_types,
-            )?;
-            let len = array_values.len();
-            (Value::Collection(array_values), len)
-        } else if is_tuple_type {
-            let tuple_values = abi_decode(&tuple_types, &encoded_value.to_vec())?;
-            let len = tuple_values.len();
-            (Value::Collection(tuple_values), len)
+        if is_dynamic_type {
+            work.charge(1)?;
+        }
+        let (encoded_value, offset) = read_head(&mut cursor, type_str, is_dynamic_type, head_end)?;
+        let value = decode_param(type_str, encoded_value, options, work)?;
+
+        if let Some(offset) = offset.filter(|_| options.validate_offsets) {
+            tail_regions.push(get_tail_region(offset, type_str, &value)?);
+        }
+        values.push(value);
+    }
+
+    if options.validate_offsets {
+        validate_tail_regions(cursor.position(), tail_regions)?;
+    }
+
+    Ok(values)
+}
+
+// Decodes truncated returndata: a `bytes` or `string` tail cut short is
+// returned with the bytes available, a string up to its last whole
+// character. The flag reports whether any was.
+pub fn abi_decode_lenient(
+    type_strs: &[&str],
+    encoded_values: &[u8],
+) -> Result<(Vec<Value>, bool), CodecError> {
+    if let Ok(values) = abi_decode(type_strs, encoded_values) {
+        return Ok((values, false));
+    }
+
+    let options = DecodeOptions {
+        truncated_dynamic_ok: true,
+        ..Default::default()
+    };
+    let values = abi_decode_with_options(type_strs, encoded_values, &options)?;
+
+    Ok((values, true))
+}
+
+// decodes a single top-level parameter, reading only its own head and tail
+pub fn read_param(type_strs: &[&str], data: &[u8], index: usize) -> Result<Value, CodecError> {
+    let type_str = type_strs
+        .get(index)
+        .ok_or_else(|| CodecError::LengthsMismatch(type_strs.len(), index.saturating_add(1)))?;
+    validate_type_syntax(type_str)?;
+    check_depth(&[type_str], DEFAULT_MAX_DEPTH)?;
+
+    let mut cursor = DecodeCursor::new(data);
+    for preceding in &type_strs[..index] {
+        cursor.skip(get_head_size(preceding)?)?;
+    }
+    let (encoded_value, _) = read_head(&mut cursor, type_str, is_dynamic(type_str), None)?;
+
+    decode_param(
+        type_str,
+        encoded_value,
+        &DecodeOptions::default(),
+        &WorkBudget::for_input(data),
+    )
+}
+
+// The head word of each element of a dynamic array, uninterpreted: offsets
+// for dynamic elements, the values themselves for static ones. `data` starts
+// at the array's length word, i.e. where the array's own offset points.
+pub fn abi_decode_array_raw(element_type: &str, data: &[u8]) -> Result<Vec<[u8; 32]>, CodecError> {
+    let size = DecodeCursor::new(data).read_offset()?;
+    abi_decode_fixed_array_raw(element_type, size, &data[32..])
+}
+
+// The same for a `T[size]`, which has no length word: `data` starts at the
+// first of its `size` heads.
+pub fn abi_decode_fixed_array_raw(
+    element_type: &str,
+    size: usize,
+    data: &[u8],
+) -> Result<Vec<[u8; 32]>, CodecError> {
+    validate_type_syntax(element_type)?;
+    if get_head_size(element_type)? != 32 {
+        return Err(CodecError::UnsupportedType(element_type.to_string()));
+    }
+
+    let mut cursor = DecodeCursor::new(data);
+    let heads_size = size.saturating_mul(32);
+    if heads_size > cursor.remaining() {
+        return Err(CodecError::OutOfBounds(heads_size, cursor.remaining()));
+    }
+
+    (0..size).map(|_| cursor.read_word()).collect()
+}
+
+// decodes one parameter from the bytes its head points at
+fn decode_param(
+    type_str: &str,
+    encoded_value: &[u8],
+    options: &DecodeOptions,
+    work: &WorkBudget,
+) -> Result<Value, CodecError> {
+    let (is_array_type, size) = is_array(type_str)?;
+    let (is_tuple_type, tuple_types) = is_tuple(type_str)?;
+
+    if is_array_type {
+        let array_values = decode_array(
+            type_str,
+            encoded_value,
+            size,
+            is_tuple_type,
+            &tuple_types,
+            options,
+            work,
+        )?;
+        Ok(Value::Collection(array_values))
+    } else if is_tuple_type {
+        let tuple_values = decode_params(&tuple_types, encoded_value, options, work)?;
+        Ok(Value::Collection(tuple_values))
+    } else {
+        decode(encoded_value, type_str, is_dynamic(type_str), options, work)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedField {
+    pub value: Value,
+    pub was_dynamic: bool,
+    // first word of the parameter's head: the offset for dynamic values
+    pub head_word: [u8; 32],
+    pub followed_offset: Option<usize>,
+}
+
+// decodes like `abi_decode` while reporting how each top-level head was read
+pub fn abi_decode_debug(
+    type_strs: &[&str],
+    encoded_values: &[u8],
+) -> Result<Vec<DecodedField>, CodecError> {
+    let values = abi_decode(type_strs, encoded_values)?;
+
+    let mut cursor = DecodeCursor::new(encoded_values);
+    let mut fields = Vec::with_capacity(values.len());
+    for (type_str, value) in type_strs.iter().zip(values) {
+        let was_dynamic = is_dynamic(type_str);
+        let mut peek = cursor;
+        let head_word = peek.read_word()?;
+        let followed_offset = if was_dynamic {
+            Some(cursor.read_offset()?)
         } else {
-            (decode(encoded_value, type_str, is_dynamic_type)?, 1)
+            cursor.skip(get_head_size(type_str)?)?;
+            None
         };
-        values.push(value);
-        cursor += size * 32;
+
+        fields.push(DecodedField {
+            value,
+            was_dynamic,
+            head_word,
+            followed_offset,
+        });
+    }
+
+    Ok(fields)
+}
+
+pub fn abi_decode_normalized(
+    type_strs: &[&str],
+    encoded_values: &[u8],
+) -> Result<Vec<Value>, CodecError> {
+    let type_strs: Vec<String> = type_strs.iter().map(|t| normalize_type_case(t)).collect();
+    let type_strs: Vec<&str> = type_strs.iter().map(|t| t.as_str()).collect();
+
+    abi_decode(&type_strs, encoded_values)
+}
+
+// decodes an abi-encoded `bytes[]` whose elements are calldata, decoding
+// each element against the signature at the same position
+pub fn abi_decode_bytes_array_as(
+    outer_data: &[u8],
+    inner_sigs: &[&str],
+) -> Result<Vec<Vec<Value>>, CodecError> {
+    let outer = abi_decode(&["bytes[]"], outer_data)?;
+    let calls = Vec::<Bytes>::from_value(&outer[0])?;
+    if calls.len() != inner_sigs.len() {
+        return Err(CodecError::LengthsMismatch(inner_sigs.len(), calls.len()));
+    }
+
+    calls
+        .iter()
+        .zip(inner_sigs)
+        .map(|(call, signature)| abi_decode_with_signature(signature, call))
+        .collect()
+}
+
+// hex-decodes JSON-RPC topics, with or without `0x`, for `abi_decode_log`
+pub fn parse_topics(topics: &[&str]) -> Result<Vec<Vec<u8>>, CodecError> {
+    topics
+        .iter()
+        .map(|topic| {
+            let bytes = hex::decode(topic).map_err(|_| {
+                CodecError::InvalidTypeAndValue("bytes32".to_string(), topic.to_string())
+            })?;
+            if bytes.len() != 32 {
+                return Err(CodecError::InvalidValueLength(bytes.len()));
+            }
+
+            Ok(bytes)
+        })
+        .collect()
+}
+
+// decodes an event log. `indexed` flags which parameters of the signature
+// live in the topics; dynamic indexed parameters are only available as their
+// keccak256 hash and are returned as bytes32
+pub fn abi_decode_log(
+    signature: &str,
+    indexed: &[bool],
+    topics: &[Vec<u8>],
+    data: &[u8],
+) -> Result<Vec<Value>, CodecError> {
+    let type_strs = get_parameter_types(signature)?;
+    if type_strs.len() != indexed.len() {
+        return Err(CodecError::LengthsMismatch(type_strs.len(), indexed.len()));
+    }
+
+    let indexed_count = indexed.iter().filter(|i| **i).count();
+    // topic0 is the signature hash, followed by one topic per indexed param
+    if topics.len() != indexed_count + 1 {
+        return Err(CodecError::TopicCountMismatch {
+            expected: indexed_count + 1,
+            got: topics.len(),
+        });
+    }
+    if topics[0] != event_topic(signature) {
+        return Err(CodecError::InvalidSelector);
+    }
+
+    decode_log_params(&type_strs, indexed, &topics[1..], data)
+}
+
+// decodes a log emitted by an anonymous event: there is no signature hash in
+// topic0, so every topic is an indexed parameter
+pub fn abi_decode_anonymous_log(
+    signature: &str,
+    indexed: &[bool],
+    topics: &[Vec<u8>],
+    data: &[u8],
+) -> Result<Vec<Value>, CodecError> {
+    let type_strs = get_parameter_types(signature)?;
+    if type_strs.len() != indexed.len() {
+        return Err(CodecError::LengthsMismatch(type_strs.len(), indexed.len()));
+    }
+
+    let indexed_count = indexed.iter().filter(|i| **i).count();
+    if topics.len() != indexed_count {
+        return Err(CodecError::TopicCountMismatch {
+            expected: indexed_count,
+            got: topics.len(),
+        });
+    }
+
+    decode_log_params(&type_strs, indexed, topics, data)
+}
+
+fn decode_log_params(
+    type_strs: &[&str],
+    indexed: &[bool],
+    topics: &[Vec<u8>],
+    data: &[u8],
+) -> Result<Vec<Value>, CodecError> {
+    let data_types: Vec<&str> = type_strs
+        .iter()
+        .zip(indexed)
+        .filter(|(_, i)| !**i)
+        .map(|(t, _)| *t)
+        .collect();
+    let mut data_values = abi_decode(&data_types, data)?.into_iter();
+    let mut topics = topics.iter();
+
+    let mut values = Vec::with_capacity(type_strs.len());
+    for (type_str, is_indexed) in type_strs.iter().zip(indexed) {
+        if *is_indexed {
+            let topic = topics.next().ok_or(CodecError::InvalidValueLength(0))?;
+            if topic.len() != 32 {
+                return Err(CodecError::InvalidValueLength(topic.len()));
+            }
+            let topic_type =
+                if is_dynamic(type_str) || is_tuple(type_str)?.0 || is_array(type_str)?.0 {
+                    "bytes32"
+                } else {
+                    type_str
+                };
+            values.extend(abi_decode(&[topic_type], topic)?);
+        } else {
+            values.extend(data_values.next());
+        }
     }
 
     Ok(values)
@@ -62,657 +534,331 @@ fn decode_array(
     arr_type_str: &str,
     encoded_values: &[u8],
     size: usize,
-    is_dynamic_type: bool,
     is_tuple_type: bool,
-    tuple_types: &Vec<&str>,
+    tuple_types: &[&str],
+    options: &DecodeOptions,
+    work: &WorkBudget,
 ) -> Result<Vec<Value>, CodecError> {
-    let mut encoded_values = encoded_values;
+    let mut cursor = DecodeCursor::new(encoded_values);
     let mut size = size;
     if size == 0 {
-        size = u64::from_be_bytes(encoded_values[24..32].try_into().unwrap()) as usize;
-        encoded_values = &encoded_values[32..];
+        work.charge(1)?;
+        size = cursor.read_offset()?;
+    }
+    if options.max_array_len.is_some_and(|max| size > max) {
+        return Err(CodecError::InvalidValueLength(size));
     }
     let type_str = arr_type_str.split("[").next().unwrap();
+    let element_type_str = get_array_element_type(arr_type_str);
+    let is_dynamic_element = is_dynamic(element_type_str);
 
-    let mut values = Vec::new();
-    let mut cursor = 0;
+    // element offsets are relative to the start of the array's elements,
+    // right after the length word for dynamic arrays
+    let mut cursor = DecodeCursor::new(cursor.rest());
+    // every element needs at least its head, so a length the buffer cannot
+    // hold is rejected before it is used to size the result
+    let heads_size = size.saturating_mul(get_head_size(element_type_str)?);
+    if heads_size > cursor.remaining() {
+        return Err(CodecError::OutOfBounds(heads_size, cursor.remaining()));
+    }
+    // each element costs at least a word, even when its head is empty
+    work.charge(size)?;
+    let head_end = if options.validate_offsets {
+        Some(heads_size)
+    } else {
+        None
+    };
+    let mut values = Vec::with_capacity(size);
+    let mut tail_regions = Vec::new();
     for _ in 0..size {
-        if is_tuple_type {
-            let tuple_encoded_values =
-                handle_offset(encoded_values, cursor, is_dynamic_type, cursor);
-            let tuple_values = abi_decode(tuple_types, &tuple_encoded_values.to_vec())?;
-            values.push(Value::Collection(tuple_values));
-            cursor += 32 * values.len();
+        let (encoded_value, offset) =
+            read_head(&mut cursor, element_type_str, is_dynamic_element, head_end)?;
+        let value = if is_tuple_type {
+            let tuple_values = decode_params(tuple_types, encoded_value, options, work)?;
+            Value::Collection(tuple_values)
         } else {
-            let encoded_value = handle_offset(encoded_values, cursor, is_dynamic_type, 0);
-            let value = decode(encoded_value, type_str, is_dynamic_type)?;
-            values.push(value);
-            cursor += 32;
+            decode(encoded_value, type_str, is_dynamic_element, options, work)?
+        };
+
+        if let Some(offset) = offset.filter(|_| options.validate_offsets) {
+            tail_regions.push(get_tail_region(offset, element_type_str, &value)?);
         }
+        values.push(value);
+    }
+
+    if options.validate_offsets {
+        validate_tail_regions(cursor.position(), tail_regions)?;
     }
 
     Ok(values)
 }
 
-fn handle_offset(
-    encoded_values: &[u8],
-    cursor: usize,
+// Returns the bytes a value is decoded from and moves the cursor past its
+// head. Dynamic values follow their offset word, whose value is returned too;
+// static values are read in place and may span several words.
+// When `head_end` is given, offsets must be word-aligned and point at or past
+// the end of the head region; well-formed encodings never point backward into it.
+fn read_head<'a>(
+    cursor: &mut DecodeCursor<'a>,
+    type_str: &str,
     is_dynamic_type: bool,
-    tuple_cursor: usize,
-) -> &[u8] {
+    head_end: Option<usize>,
+) -> Result<(&'a [u8], Option<usize>), CodecError> {
     if is_dynamic_type {
-        let offset =
-            u64::from_be_bytes(encoded_values[cursor + 24..cursor + 32].try_into().unwrap())
-                as usize;
-        &encoded_values[offset + tuple_cursor..]
+        let offset = cursor.read_offset()?;
+        if let Some(head_end) = head_end.filter(|head_end| offset < *head_end) {
+            return Err(CodecError::MalformedOffsets(format!(
+                "offset {} points into the head ending at {}",
+                offset, head_end
+            )));
+        }
+        // encoders only ever point at word boundaries
+        if head_end.is_some() && offset % 32 != 0 {
+            return Err(CodecError::MalformedOffsets(format!(
+                "offset {} is not a multiple of 32",
+                offset
+            )));
+        }
+        Ok((cursor.tail(offset)?, Some(offset)))
     } else {
-        &encoded_values[cursor..cursor + 32]
+        let encoded_value = cursor.rest();
+        cursor.skip(get_head_size(type_str)?)?;
+        Ok((encoded_value, None))
+    }
+}
+
+// The region a dynamic value claims is measured by its canonical encoding,
+// without the offset word that points to it.
+fn get_tail_region(
+    offset: usize,
+    type_str: &str,
+    value: &Value,
+) -> Result<(usize, usize), CodecError> {
+    let tail_length = abi_encode(&[type_str], std::slice::from_ref(value))?.len() - 32;
+
+    Ok((offset, offset + tail_length))
+}
+
+fn validate_tail_regions(
+    head_size: usize,
+    mut tail_regions: Vec<(usize, usize)>,
+) -> Result<(), CodecError> {
+    tail_regions.sort();
+
+    let mut expected_start = head_size;
+    for (start, end) in tail_regions {
+        if start != expected_start {
+            return Err(CodecError::MalformedOffsets(format!(
+                "tail at {} expected at {}",
+                start, expected_start
+            )));
+        }
+        expected_start = end;
     }
+
+    Ok(())
 }
 
 fn decode(
     encoded_value: &[u8],
     type_str: &str,
     is_dynamic_type: bool,
+    options: &DecodeOptions,
+    work: &WorkBudget,
 ) -> Result<Value, CodecError> {
-    let inner_value = if is_dynamic_type {
-        let length = u64::from_be_bytes(encoded_value[24..32].try_into().unwrap());
-        &encoded_value[32..32 + length as usize]
+    let mut cursor = DecodeCursor::new(encoded_value);
+    if is_dynamic_type {
+        let length = cursor.read_offset()?;
+        work.charge(1 + length.min(cursor.remaining()).div_ceil(32))?;
+        if options.truncated_dynamic_ok && length > cursor.remaining() {
+            return decode_packed(truncated_tail(cursor.rest(), type_str), type_str);
+        }
+        let data = cursor.read_bytes(length)?;
+        if options.validate_padding {
+            let padding = cursor.read_bytes(length.div_ceil(32) * 32 - length)?;
+            if padding.iter().any(|b| *b != 0) {
+                return Err(CodecError::DirtyPaddingBytes);
+            }
+        }
+        return decode_packed(data, type_str);
+    }
+
+    work.charge(1)?;
+    let word = cursor.read_word()?;
+    let length = get_bytes_from_type(type_str);
+    if length == 0 && options.raw_on_unsupported {
+        return Ok(Value::Single(
+            Box::new(FixedBytes::<32>::from(word)),
+            "raw".to_string(),
+        ));
+    }
+    // fixed-size bytes sit in the high-order bytes of the word, everything
+    // else in the low-order bytes
+    let (data, padding) = if is_fixed_bytes(type_str) {
+        word.split_at(length)
     } else {
-        let length = get_bytes_from_type(type_str);
-        &encoded_value[32 - length..32]
+        let (padding, data) = word.split_at(32 - length);
+        (data, padding)
     };
+    if options.validate_padding && !padding.is_empty() {
+        // signed numbers are sign-extended, everything else zero-padded
+        let negative = is_signed(type_str) && data.first().is_some_and(|b| b & 0x80 != 0);
+        let fill = if negative { 0xff } else { 0x00 };
+        if padding.iter().any(|b| *b != fill) {
+            return Err(CodecError::DirtyPaddingBytes);
+        }
+    }
 
-    decode_packed(inner_value, type_str)
+    decode_packed(data, type_str)
+}
+
+// Builds a boxed value of the alloy type matching a byte width, reading the
+// first `width` bytes of the input.
+macro_rules! decode_sized {
+    ($encoded_value:expr, $width:expr, $($w:literal => $t:ty),* $(,)?) => {
+        match $width {
+            $($w => Some(Box::new(<$t>::from_bytes::<$w>(
+                $encoded_value[..$w].try_into().unwrap(),
+            )) as Box<dyn BoxTrait>),)*
+            _ => None,
+        }
+    };
 }
 
 fn decode_packed(encoded_value: &[u8], type_str: &str) -> Result<Value, CodecError> {
-    match type_str {
-        "address" => Ok(Value::Single(
-            Box::new(Address::from_bytes::<20>(
-                encoded_value[..20].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes" => Ok(Value::Single(
-            Box::new(Bytes::copy_from_slice(encoded_value)),
-            type_str.to_string(),
-        )),
-        "string" => {
-            let string = String::from_utf8(encoded_value.to_vec()).unwrap();
-            Ok(Value::Single(Box::new(string), type_str.to_string()))
+    let value: Box<dyn BoxTrait> = match type_str {
+        "address" => Box::new(Address::from_bytes::<20>(
+            encoded_value[..20].try_into().unwrap(),
+        )),
+        "bytes" => Box::new(Bytes::copy_from_slice(encoded_value)),
+        "string" => Box::new(String::from_utf8(encoded_value.to_vec()).map_err(|_| {
+            CodecError::InvalidTypeAndValue(
+                "string".to_string(),
+                hex::encode_prefixed(encoded_value),
+            )
+        })?),
+        "bool" => Box::new(bool::from_bytes::<1>(
+            encoded_value[..1].try_into().unwrap(),
+        )),
+        "function" => Box::new(FixedBytes::<24>::from_bytes::<24>(
+            encoded_value[..24].try_into().unwrap(),
+        )),
+        _ => decode_packed_sized(encoded_value, type_str)
+            .ok_or_else(|| CodecError::UnsupportedType(type_str.to_string()))?,
+    };
+
+    Ok(Value::Single(value, type_str.to_string()))
+}
+
+// a cut can land inside a multi-byte character, so a truncated string keeps
+// only its complete characters
+fn truncated_tail<'a>(data: &'a [u8], type_str: &str) -> &'a [u8] {
+    match std::str::from_utf8(data) {
+        Err(err) if type_str == "string" && err.error_len().is_none() => &data[..err.valid_up_to()],
+        _ => data,
+    }
+}
+
+// uintN, intN, bytesN and the fixed-point types: the width is parsed once
+// and dispatched on, rather than comparing the type string against every
+// sized type
+fn decode_packed_sized(encoded_value: &[u8], type_str: &str) -> Option<Box<dyn BoxTrait>> {
+    if let Some(fixed) = FixedPoint::from_be_slice(type_str, encoded_value) {
+        return Some(Box::new(fixed));
+    }
+
+    if let Some(bits) = type_str.strip_prefix("uint").and_then(parse_width) {
+        if bits % 8 != 0 {
+            return None;
         }
-        "bool" => Ok(Value::Single(
-            Box::new(bool::from_bytes::<1>(
-                encoded_value[..1].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint8" => Ok(Value::Single(
-            Box::new(U8::from_bytes::<1>(encoded_value[..1].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint16" => Ok(Value::Single(
-            Box::new(U16::from_bytes::<2>(encoded_value[..2].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint24" => Ok(Value::Single(
-            Box::new(U24::from_bytes::<3>(encoded_value[..3].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint32" => Ok(Value::Single(
-            Box::new(U32::from_bytes::<4>(encoded_value[..4].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint40" => Ok(Value::Single(
-            Box::new(U40::from_bytes::<5>(encoded_value[..5].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint48" => Ok(Value::Single(
-            Box::new(U48::from_bytes::<6>(encoded_value[..6].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint56" => Ok(Value::Single(
-            Box::new(U56::from_bytes::<7>(encoded_value[..7].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint64" => Ok(Value::Single(
-            Box::new(U64::from_bytes::<8>(encoded_value[..8].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint72" => Ok(Value::Single(
-            Box::new(U72::from_bytes::<9>(encoded_value[..9].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "uint80" => Ok(Value::Single(
-            Box::new(U80::from_bytes::<10>(
-                encoded_value[..10].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint88" => Ok(Value::Single(
-            Box::new(U88::from_bytes::<11>(
-                encoded_value[..11].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint96" => Ok(Value::Single(
-            Box::new(U96::from_bytes::<12>(
-                encoded_value[..12].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint104" => Ok(Value::Single(
-            Box::new(U104::from_bytes::<13>(
-                encoded_value[..13].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint112" => Ok(Value::Single(
-            Box::new(U112::from_bytes::<14>(
-                encoded_value[..14].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint120" => Ok(Value::Single(
-            Box::new(U120::from_bytes::<15>(
-                encoded_value[..15].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint128" => Ok(Value::Single(
-            Box::new(U128::from_bytes::<16>(
-                encoded_value[..16].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint136" => Ok(Value::Single(
-            Box::new(U136::from_bytes::<17>(
-                encoded_value[..17].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint144" => Ok(Value::Single(
-            Box::new(U144::from_bytes::<18>(
-                encoded_value[..18].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint152" => Ok(Value::Single(
-            Box::new(U152::from_bytes::<19>(
-                encoded_value[..19].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint160" => Ok(Value::Single(
-            Box::new(U160::from_bytes::<20>(
-                encoded_value[..20].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint168" => Ok(Value::Single(
-            Box::new(U168::from_bytes::<21>(
-                encoded_value[..21].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint176" => Ok(Value::Single(
-            Box::new(U176::from_bytes::<22>(
-                encoded_value[..22].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint184" => Ok(Value::Single(
-            Box::new(U184::from_bytes::<23>(
-                encoded_value[..23].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint192" => Ok(Value::Single(
-            Box::new(U192::from_bytes::<24>(
-                encoded_value[..24].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint200" => Ok(Value::Single(
-            Box::new(U200::from_bytes::<25>(
-                encoded_value[..25].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint208" => Ok(Value::Single(
-            Box::new(U208::from_bytes::<26>(
-                encoded_value[..26].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint216" => Ok(Value::Single(
-            Box::new(U216::from_bytes::<27>(
-                encoded_value[..27].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint224" => Ok(Value::Single(
-            Box::new(U224::from_bytes::<28>(
-                encoded_value[..28].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint232" => Ok(Value::Single(
-            Box::new(U232::from_bytes::<29>(
-                encoded_value[..29].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint240" => Ok(Value::Single(
-            Box::new(U240::from_bytes::<30>(
-                encoded_value[..30].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint248" => Ok(Value::Single(
-            Box::new(U248::from_bytes::<31>(
-                encoded_value[..31].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "uint256" => Ok(Value::Single(
-            Box::new(U256::from_bytes::<32>(
-                encoded_value[..32].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int8" => Ok(Value::Single(
-            Box::new(I8::from_bytes::<1>(encoded_value[..1].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int16" => Ok(Value::Single(
-            Box::new(I16::from_bytes::<2>(encoded_value[..2].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int24" => Ok(Value::Single(
-            Box::new(I24::from_bytes::<3>(encoded_value[..3].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int32" => Ok(Value::Single(
-            Box::new(I32::from_bytes::<4>(encoded_value[..4].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int40" => Ok(Value::Single(
-            Box::new(I40::from_bytes::<5>(encoded_value[..5].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int48" => Ok(Value::Single(
-            Box::new(I48::from_bytes::<6>(encoded_value[..6].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int56" => Ok(Value::Single(
-            Box::new(I56::from_bytes::<7>(encoded_value[..7].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int64" => Ok(Value::Single(
-            Box::new(I64::from_bytes::<8>(encoded_value[..8].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int72" => Ok(Value::Single(
-            Box::new(I72::from_bytes::<9>(encoded_value[..9].try_into().unwrap())),
-            type_str.to_string(),
-        )),
-        "int80" => Ok(Value::Single(
-            Box::new(I80::from_bytes::<10>(
-                encoded_value[..10].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int88" => Ok(Value::Single(
-            Box::new(I88::from_bytes::<11>(
-                encoded_value[..11].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int96" => Ok(Value::Single(
-            Box::new(I96::from_bytes::<12>(
-                encoded_value[..12].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int104" => Ok(Value::Single(
-            Box::new(I104::from_bytes::<13>(
-                encoded_value[..13].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int112" => Ok(Value::Single(
-            Box::new(I112::from_bytes::<14>(
-                encoded_value[..14].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int120" => Ok(Value::Single(
-            Box::new(I120::from_bytes::<15>(
-                encoded_value[..15].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int128" => Ok(Value::Single(
-            Box::new(I128::from_bytes::<16>(
-                encoded_value[..16].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int136" => Ok(Value::Single(
-            Box::new(I136::from_bytes::<17>(
-                encoded_value[..17].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int144" => Ok(Value::Single(
-            Box::new(I144::from_bytes::<18>(
-                encoded_value[..18].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int152" => Ok(Value::Single(
-            Box::new(I152::from_bytes::<19>(
-                encoded_value[..19].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int160" => Ok(Value::Single(
-            Box::new(I160::from_bytes::<20>(
-                encoded_value[..20].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int168" => Ok(Value::Single(
-            Box::new(I168::from_bytes::<21>(
-                encoded_value[..21].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int176" => Ok(Value::Single(
-            Box::new(I176::from_bytes::<22>(
-                encoded_value[..22].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int184" => Ok(Value::Single(
-            Box::new(I184::from_bytes::<23>(
-                encoded_value[..23].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int192" => Ok(Value::Single(
-            Box::new(I192::from_bytes::<24>(
-                encoded_value[..24].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int200" => Ok(Value::Single(
-            Box::new(I200::from_bytes::<25>(
-                encoded_value[..25].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int208" => Ok(Value::Single(
-            Box::new(I208::from_bytes::<26>(
-                encoded_value[..26].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int216" => Ok(Value::Single(
-            Box::new(I216::from_bytes::<27>(
-                encoded_value[..27].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int224" => Ok(Value::Single(
-            Box::new(I224::from_bytes::<28>(
-                encoded_value[..28].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int232" => Ok(Value::Single(
-            Box::new(I232::from_bytes::<29>(
-                encoded_value[..29].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int240" => Ok(Value::Single(
-            Box::new(I240::from_bytes::<30>(
-                encoded_value[..30].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int248" => Ok(Value::Single(
-            Box::new(I248::from_bytes::<31>(
-                encoded_value[..31].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "int256" => Ok(Value::Single(
-            Box::new(I256::from_bytes::<32>(
-                encoded_value[..32].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes1" => Ok(Value::Single(
-            Box::new(FixedBytes::<1>::from_bytes::<1>(
-                encoded_value[..1].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes2" => Ok(Value::Single(
-            Box::new(FixedBytes::<2>::from_bytes::<2>(
-                encoded_value[..2].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes3" => Ok(Value::Single(
-            Box::new(FixedBytes::<3>::from_bytes::<3>(
-                encoded_value[..3].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes4" => Ok(Value::Single(
-            Box::new(FixedBytes::<4>::from_bytes::<4>(
-                encoded_value[..4].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes5" => Ok(Value::Single(
-            Box::new(FixedBytes::<5>::from_bytes::<5>(
-                encoded_value[..5].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes6" => Ok(Value::Single(
-            Box::new(FixedBytes::<6>::from_bytes::<6>(
-                encoded_value[..6].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes7" => Ok(Value::Single(
-            Box::new(FixedBytes::<7>::from_bytes::<7>(
-                encoded_value[..7].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes8" => Ok(Value::Single(
-            Box::new(FixedBytes::<8>::from_bytes::<8>(
-                encoded_value[..8].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes9" => Ok(Value::Single(
-            Box::new(FixedBytes::<9>::from_bytes::<9>(
-                encoded_value[..9].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes10" => Ok(Value::Single(
-            Box::new(FixedBytes::<10>::from_bytes::<10>(
-                encoded_value[..10].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes11" => Ok(Value::Single(
-            Box::new(FixedBytes::<11>::from_bytes::<11>(
-                encoded_value[..11].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes12" => Ok(Value::Single(
-            Box::new(FixedBytes::<12>::from_bytes::<12>(
-                encoded_value[..12].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes13" => Ok(Value::Single(
-            Box::new(FixedBytes::<13>::from_bytes::<13>(
-                encoded_value[..13].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes14" => Ok(Value::Single(
-            Box::new(FixedBytes::<14>::from_bytes::<14>(
-                encoded_value[..14].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes15" => Ok(Value::Single(
-            Box::new(FixedBytes::<15>::from_bytes::<15>(
-                encoded_value[..15].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes16" => Ok(Value::Single(
-            Box::new(FixedBytes::<16>::from_bytes::<16>(
-                encoded_value[..16].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes17" => Ok(Value::Single(
-            Box::new(FixedBytes::<17>::from_bytes::<17>(
-                encoded_value[..17].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes18" => Ok(Value::Single(
-            Box::new(FixedBytes::<18>::from_bytes::<18>(
-                encoded_value[..18].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes19" => Ok(Value::Single(
-            Box::new(FixedBytes::<19>::from_bytes::<19>(
-                encoded_value[..19].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes20" => Ok(Value::Single(
-            Box::new(FixedBytes::<20>::from_bytes::<20>(
-                encoded_value[..20].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes21" => Ok(Value::Single(
-            Box::new(FixedBytes::<21>::from_bytes::<21>(
-                encoded_value[..21].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes22" => Ok(Value::Single(
-            Box::new(FixedBytes::<22>::from_bytes::<22>(
-                encoded_value[..22].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes23" => Ok(Value::Single(
-            Box::new(FixedBytes::<23>::from_bytes::<23>(
-                encoded_value[..23].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes24" => Ok(Value::Single(
-            Box::new(FixedBytes::<24>::from_bytes::<24>(
-                encoded_value[..24].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes25" => Ok(Value::Single(
-            Box::new(FixedBytes::<25>::from_bytes::<25>(
-                encoded_value[..25].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes26" => Ok(Value::Single(
-            Box::new(FixedBytes::<26>::from_bytes::<26>(
-                encoded_value[..26].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes27" => Ok(Value::Single(
-            Box::new(FixedBytes::<27>::from_bytes::<27>(
-                encoded_value[..27].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes28" => Ok(Value::Single(
-            Box::new(FixedBytes::<28>::from_bytes::<28>(
-                encoded_value[..28].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes29" => Ok(Value::Single(
-            Box::new(FixedBytes::<29>::from_bytes::<29>(
-                encoded_value[..29].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes30" => Ok(Value::Single(
-            Box::new(FixedBytes::<30>::from_bytes::<30>(
-                encoded_value[..30].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes31" => Ok(Value::Single(
-            Box::new(FixedBytes::<31>::from_bytes::<31>(
-                encoded_value[..31].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        "bytes32" => Ok(Value::Single(
-            Box::new(FixedBytes::<32>::from_bytes::<32>(
-                encoded_value[..32].try_into().unwrap(),
-            )),
-            type_str.to_string(),
-        )),
-        _ => Err(CodecError::UnsupportedType(type_str.to_string())),
+        return decode_sized!(encoded_value, bits / 8,
+            1 => U8, 2 => U16, 3 => U24, 4 => U32, 5 => U40, 6 => U48, 7 => U56, 8 => U64,
+            9 => U72, 10 => U80, 11 => U88, 12 => U96, 13 => U104, 14 => U112, 15 => U120,
+            16 => U128, 17 => U136, 18 => U144, 19 => U152, 20 => U160, 21 => U168,
+            22 => U176, 23 => U184, 24 => U192, 25 => U200, 26 => U208, 27 => U216,
+            28 => U224, 29 => U232, 30 => U240, 31 => U248, 32 => U256,
+        );
     }
+
+    if let Some(bits) = type_str.strip_prefix("int").and_then(parse_width) {
+        if bits % 8 != 0 {
+            return None;
+        }
+        return decode_sized!(encoded_value, bits / 8,
+            1 => I8, 2 => I16, 3 => I24, 4 => I32, 5 => I40, 6 => I48, 7 => I56, 8 => I64,
+            9 => I72, 10 => I80, 11 => I88, 12 => I96, 13 => I104, 14 => I112, 15 => I120,
+            16 => I128, 17 => I136, 18 => I144, 19 => I152, 20 => I160, 21 => I168,
+            22 => I176, 23 => I184, 24 => I192, 25 => I200, 26 => I208, 27 => I216,
+            28 => I224, 29 => I232, 30 => I240, 31 => I248, 32 => I256,
+        );
+    }
+
+    let size = type_str.strip_prefix("bytes").and_then(parse_width)?;
+    decode_sized!(encoded_value, size,
+        1 => FixedBytes<1>, 2 => FixedBytes<2>, 3 => FixedBytes<3>, 4 => FixedBytes<4>,
+        5 => FixedBytes<5>, 6 => FixedBytes<6>, 7 => FixedBytes<7>, 8 => FixedBytes<8>,
+        9 => FixedBytes<9>, 10 => FixedBytes<10>, 11 => FixedBytes<11>, 12 => FixedBytes<12>,
+        13 => FixedBytes<13>, 14 => FixedBytes<14>, 15 => FixedBytes<15>, 16 => FixedBytes<16>,
+        17 => FixedBytes<17>, 18 => FixedBytes<18>, 19 => FixedBytes<19>, 20 => FixedBytes<20>,
+        21 => FixedBytes<21>, 22 => FixedBytes<22>, 23 => FixedBytes<23>, 24 => FixedBytes<24>,
+        25 => FixedBytes<25>, 26 => FixedBytes<26>, 27 => FixedBytes<27>, 28 => FixedBytes<28>,
+        29 => FixedBytes<29>, 30 => FixedBytes<30>, 31 => FixedBytes<31>, 32 => FixedBytes<32>,
+    )
+}
+
+// widths are written without sign or leading zeros, e.g. the 64 in `uint64`
+fn parse_width(digits: &str) -> Option<usize> {
+    if digits.starts_with('0') || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
 }
 
 #[cfg(test)]
 mod encode_tests {
     use super::*;
+    use crate::codec::traits::{BoxTrait, EncodeCodec};
+    use crate::codec::types::{ValueBuilder, create_value, string_array, uint_array};
+    use crate::cursor::read_usize;
+    use crate::encode::{abi_encode_with_max_depth, abi_encode_with_singature};
     use alloy_primitives::hex;
 
     #[test]
     fn test_decode() {
-        let mut value = hex!(
+        let value = hex!(
             "0x000000000000000000000000000000000000000000000000000000000000000c48656c6c6f20576f726c64210000000000000000000000000000000000000000"
         );
-        let value = decode(&mut value[..], "string", true).unwrap();
-        println!("{:?}", value);
-        assert!(false);
+        let value = decode(
+            &value[..],
+            "string",
+            true,
+            &DecodeOptions::default(),
+            &WorkBudget::for_input(&value),
+        )
+        .unwrap();
+        assert_eq!(value.eth_type(), "string");
+        assert_eq!(value.to_string(), "Hello World!");
     }
 
     #[test]
     fn test_decode_packed() {
-        let mut value = hex!("0x000000000000000000000000000000000000000000000000000000000000000c");
-        let value = decode_packed(&mut value[..], "uint256").unwrap();
-        println!("{:?}", value);
-        assert!(false);
+        let value = hex!("0x000000000000000000000000000000000000000000000000000000000000000c");
+        let value = decode_packed(&value[..], "uint256").unwrap();
+        assert_eq!(value.eth_type(), "uint256");
+        assert_eq!(value.to_string(), "12");
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8_string() {
+        let invalid = || CodecError::InvalidTypeAndValue("string".to_string(), "0xff".to_string());
+        assert_eq!(decode_packed(&[0xff], "string").err(), Some(invalid()));
+
+        let mut encoded = [0u8; 96];
+        encoded[31] = 0x20;
+        encoded[63] = 0x01;
+        encoded[64] = 0xff;
+        assert_eq!(abi_decode(&["string"], &encoded).err(), Some(invalid()));
     }
 
     #[test]
@@ -721,8 +867,1326 @@ mod encode_tests {
             "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
         );
         let type_strs = vec!["address", "(string[],uint256,uint8)[]", "uint256"];
-        let value = abi_decode(&type_strs, &value.to_vec()).unwrap();
-        println!("{:?}", value);
-        assert!(false);
+        let decoded = abi_decode(&type_strs, &value).unwrap();
+        assert_eq!(
+            decoded
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "0x0000000000000000000000000000000000000000",
+                "Hello, world!, Hello, world!, 1, 1",
+                "1"
+            ]
+        );
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), value.to_vec());
+    }
+
+    #[test]
+    fn test_abi_decode_bytes_array() {
+        let type_strs = vec!["bytes[]"];
+        let values = ValueBuilder::new()
+            .add_array(vec![
+                Bytes::from(hex!("deadbeef")),
+                Bytes::from(vec![0x11; 40]),
+            ])
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000004deadbeef00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002811111111111111111111111111111111111111111111111111111111111111111111111111111111000000000000000000000000000000000000000000000000"
+        );
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_returns_named() {
+        let values = ValueBuilder::new()
+            .add(U112::from(1_000))
+            .add(U112::from(2_000))
+            .add(U32::from(1_700_000_000u32))
+            .build();
+        let data = abi_encode(&["uint112", "uint112", "uint32"], &values).unwrap();
+
+        let named = abi_decode_returns_named(
+            "getReserves() returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)",
+            &data,
+        )
+        .unwrap();
+        let names: Vec<&str> = named.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["reserve0", "reserve1", "blockTimestampLast"]);
+        assert_eq!(named[1].1.to_string(), "2000");
+        assert_eq!(named[2].1.eth_type(), "uint32");
+
+        let unnamed = abi_decode_returns_named("f() returns (uint112, uint112, uint32)", &data);
+        assert_eq!(unnamed.unwrap()[2].0, "2");
+    }
+
+    #[test]
+    fn test_best_decode() {
+        // both signatures hash to the 0xa9059cbb selector
+        let candidates = ["many_msg_babbage(bytes1)", "transfer(address,uint256)"];
+        assert_eq!(
+            crate::encode::abi_encode_selector(candidates[0]).unwrap(),
+            crate::encode::abi_encode_selector(candidates[1]).unwrap()
+        );
+
+        let values = ValueBuilder::new()
+            .add(Address::repeat_byte(0x5a))
+            .add(U256::from(500))
+            .build();
+        let calldata = crate::encode::abi_encode_with_singature(candidates[1], &values).unwrap();
+
+        let (signature, decoded) = best_decode(&calldata, &candidates).unwrap();
+        assert_eq!(signature, "transfer(address,uint256)");
+        assert_eq!(U256::from_value(&decoded[1]).unwrap(), U256::from(500));
+
+        assert!(best_decode(&calldata, &["approve(address,uint256)"]).is_none());
+        assert!(best_decode(&calldata[..3], &candidates).is_none());
+    }
+
+    #[test]
+    fn test_abi_decode_batch() {
+        let signature = "Deposit(address,string)";
+        let payload = |memo: &str| {
+            let values = ValueBuilder::new()
+                .add(Address::repeat_byte(0x07))
+                .add(memo.to_string())
+                .build();
+            abi_encode(&["address", "string"], &values).unwrap()
+        };
+        let truncated = payload("second")[..70].to_vec();
+
+        let results = abi_decode_batch(signature, &[payload("first"), truncated, payload("third")]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()[1].to_string(), "first");
+        assert!(matches!(results[1], Err(CodecError::OutOfBounds(_, _))));
+        assert_eq!(results[2].as_ref().unwrap()[1].to_string(), "third");
+    }
+
+    #[test]
+    fn test_abi_decode_auto() {
+        let signature = "approve(address,uint256)";
+        let values = ValueBuilder::new()
+            .add(Address::repeat_byte(0x33))
+            .add(U256::from(1_000))
+            .build();
+        let with_selector = crate::encode::abi_encode_with_singature(signature, &values).unwrap();
+        let without_selector = abi_encode(&["address", "uint256"], &values).unwrap();
+
+        for data in [&with_selector, &without_selector] {
+            let decoded = abi_decode_auto(signature, data).unwrap();
+            assert_eq!(
+                Address::from_value(&decoded[0]).unwrap(),
+                Address::repeat_byte(0x33)
+            );
+            assert_eq!(U256::from_value(&decoded[1]).unwrap(), U256::from(1_000));
+        }
+    }
+
+    #[test]
+    fn test_abi_decode_bytes_array_as() {
+        let transfer = crate::encode::abi_encode_with_singature(
+            "transfer(address,uint256)",
+            &ValueBuilder::new()
+                .add(Address::repeat_byte(0x22))
+                .add(U256::from(500))
+                .build(),
+        )
+        .unwrap();
+        let set_name = crate::encode::abi_encode_with_singature(
+            "setName(string)",
+            &ValueBuilder::new().add("vault".to_string()).build(),
+        )
+        .unwrap();
+        let outer = abi_encode(
+            &["bytes[]"],
+            &ValueBuilder::new()
+                .add_array(vec![Bytes::from(transfer), Bytes::from(set_name)])
+                .build(),
+        )
+        .unwrap();
+
+        let inner_sigs = ["transfer(address,uint256)", "setName(string)"];
+        let decoded = abi_decode_bytes_array_as(&outer, &inner_sigs).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(
+            Address::from_value(&decoded[0][0]).unwrap(),
+            Address::repeat_byte(0x22)
+        );
+        assert_eq!(U256::from_value(&decoded[0][1]).unwrap(), U256::from(500));
+        assert_eq!(String::from_value(&decoded[1][0]).unwrap(), "vault");
+
+        assert_eq!(
+            abi_decode_bytes_array_as(&outer, &inner_sigs[..1]).err(),
+            Some(CodecError::LengthsMismatch(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_static_tuple_array() {
+        let type_strs = vec!["(uint256,address)[]"];
+        let values = ValueBuilder::new()
+            .add_array(vec![
+                vec![
+                    Box::new(U256::from(1)) as Box<dyn BoxTrait>,
+                    Box::new(Address::repeat_byte(0x11)) as Box<dyn BoxTrait>,
+                ],
+                vec![
+                    Box::new(U256::from(2)) as Box<dyn BoxTrait>,
+                    Box::new(Address::ZERO) as Box<dyn BoxTrait>,
+                ],
+            ])
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000001000000000000000000000000111111111111111111111111111111111111111100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000"
+        );
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_dynamic_tuple_array() {
+        let type_strs = vec!["(uint256,bytes)[]"];
+        let values = ValueBuilder::new()
+            .add_array(vec![
+                vec![
+                    Box::new(U256::from(1)) as Box<dyn BoxTrait>,
+                    Box::new(Bytes::from(hex!("dead"))) as Box<dyn BoxTrait>,
+                ],
+                vec![
+                    Box::new(U256::from(2)) as Box<dyn BoxTrait>,
+                    Box::new(Bytes::from(hex!("beef"))) as Box<dyn BoxTrait>,
+                ],
+            ])
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000c0000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000002dead000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000002beef000000000000000000000000000000000000000000000000000000000000"
+        );
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_fixed_dynamic_tuple_array() {
+        let type_strs = vec!["(uint256,bytes)[3]"];
+        let values = ValueBuilder::new()
+            .add_array(vec![
+                vec![
+                    Box::new(U256::from(1)) as Box<dyn BoxTrait>,
+                    Box::new(Bytes::from(hex!("aa"))) as Box<dyn BoxTrait>,
+                ],
+                vec![
+                    Box::new(U256::from(2)) as Box<dyn BoxTrait>,
+                    Box::new(Bytes::from(hex!("bbbb"))) as Box<dyn BoxTrait>,
+                ],
+                vec![
+                    Box::new(U256::from(3)) as Box<dyn BoxTrait>,
+                    Box::new(Bytes::from(hex!("cccccc"))) as Box<dyn BoxTrait>,
+                ],
+            ])
+            .build();
+
+        let word = |n: u64| hex::encode(U256::from(n).to_be_bytes::<32>());
+        let data = |d: &str| format!("{:0<64}", d);
+        // no length word: the three tuple offsets follow the outer offset directly
+        let expected = [
+            word(0x20),
+            word(0x60),
+            word(0xe0),
+            word(0x160),
+            word(1),
+            word(0x40),
+            word(1),
+            data("aa"),
+            word(2),
+            word(0x40),
+            word(2),
+            data("bbbb"),
+            word(3),
+            word(0x40),
+            word(3),
+            data("cccccc"),
+        ]
+        .concat();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(hex::encode(&encoded), expected);
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_static_bytes32_tuple_array() {
+        let type_strs = vec!["(bytes32,address)[3]"];
+        let values = ValueBuilder::new()
+            .add_array(
+                (1u8..=3)
+                    .map(|i| {
+                        vec![
+                            Box::new(FixedBytes::<32>::repeat_byte(i)) as Box<dyn BoxTrait>,
+                            Box::new(Address::repeat_byte(i)) as Box<dyn BoxTrait>,
+                        ]
+                    })
+                    .collect(),
+            )
+            .build();
+
+        // fully static: six head words, no offsets
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(encoded.len(), 6 * 32);
+        assert_eq!(&encoded[..32], &[0x01; 32]);
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+    }
+
+    #[test]
+    fn test_abi_decode_normalized() {
+        let type_strs = vec!["UINT256", "Address"];
+        let values = ValueBuilder::new()
+            .add(U256::from(1))
+            .add(Address::ZERO)
+            .build();
+        let encoded = abi_encode(&["uint256", "address"], &values).unwrap();
+
+        assert_eq!(
+            abi_decode(&type_strs, &encoded).unwrap_err(),
+            CodecError::UnsupportedType("UINT256".to_string())
+        );
+        let decoded = abi_decode_normalized(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].eth_type(), "uint256");
+        assert_eq!(decoded[1].eth_type(), "address");
+    }
+
+    #[test]
+    fn test_abi_decode_overlapping_offsets() {
+        let type_strs = vec!["string", "string"];
+        let encoded = hex!(
+            "00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000568656c6c6f000000000000000000000000000000000000000000000000000000"
+        );
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "hello");
+        assert_eq!(decoded[1].to_string(), "hello");
+        assert_eq!(
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap_err(),
+            CodecError::MalformedOffsets("tail at 64 expected at 128".to_string())
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_backward_offset() {
+        // the string's offset points back at the uint256 head word
+        let type_strs = vec!["uint256", "string"];
+        let encoded = hex!(
+            "0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000068690000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        );
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[1].to_string(), "\0\0");
+        assert_eq!(
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap_err(),
+            CodecError::MalformedOffsets("offset 0 points into the head ending at 64".to_string())
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_strict_offsets() {
+        let type_strs = vec!["address", "(string[],uint256,uint8)[]", "bytes[]"];
+        let values = ValueBuilder::new()
+            .add(Address::ZERO)
+            .add_array(vec![vec![
+                Box::new(vec![String::from("Hello"), String::from("world!")]) as Box<dyn BoxTrait>,
+                Box::new(U256::from(1)) as Box<dyn BoxTrait>,
+                Box::new(U8::from(1)) as Box<dyn BoxTrait>,
+            ]])
+            .add_array(vec![Bytes::from(hex!("dead")), Bytes::from(vec![0x11; 40])])
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_max_array_len() {
+        let type_strs = vec!["uint256[]"];
+        let encoded = hex!(
+            "000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000003e8"
+        );
+        let options = DecodeOptions {
+            max_array_len: Some(10),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            abi_decode_with_options(&type_strs, &encoded, &options).unwrap_err(),
+            CodecError::InvalidValueLength(1000)
+        );
+    }
+
+    #[test]
+    fn test_decode_packed_sized_types() {
+        let encoded = [0x7f; 32];
+        for width in 1..=32 {
+            for type_str in [
+                format!("uint{}", width * 8),
+                format!("int{}", width * 8),
+                format!("bytes{}", width),
+            ] {
+                let value = decode_packed(&encoded, &type_str).unwrap();
+                assert_eq!(value.eth_type(), type_str);
+                assert_eq!(value.to_bytes_vec(), encoded[..width].to_vec());
+            }
+        }
+
+        for type_str in [
+            "uint", "uint7", "uint264", "uint08", "int+8", "bytes0", "bytes33",
+        ] {
+            assert_eq!(
+                decode_packed(&encoded, type_str).unwrap_err(),
+                CodecError::UnsupportedType(type_str.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_abi_decode_large_array() {
+        let numbers: Vec<U256> = (0..5_000u64).map(U256::from).collect();
+        let strings: Vec<String> = (0..500).map(|i| format!("item {}", i)).collect();
+        let type_strs = ["uint256[]", "string[]"];
+        let values = ValueBuilder::new()
+            .add_array(numbers.clone())
+            .add_array(strings.clone())
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(Vec::<U256>::from_value(&decoded[0]).unwrap(), numbers);
+        assert_eq!(Vec::<String>::from_value(&decoded[1]).unwrap(), strings);
+
+        // a declared length far beyond the buffer fails before allocating
+        let mut huge = encoded.clone();
+        huge[64..96].copy_from_slice(&U256::from(u32::MAX).to_be_bytes::<32>());
+        assert_eq!(
+            abi_decode(&type_strs, &huge).unwrap_err(),
+            CodecError::OutOfBounds(u32::MAX as usize * 32, encoded.len() - 96)
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_fixed_bytes() {
+        let encoded = hex!("deadbeef00000000000000000000000000000000000000000000000000000000");
+        let decoded = abi_decode(&["bytes4"], &encoded).unwrap();
+        assert_eq!(
+            FixedBytes::<4>::from_value(&decoded[0]).unwrap(),
+            FixedBytes::from(hex!("deadbeef"))
+        );
+
+        let array = [
+            format!("{:064x}", 0x20),
+            format!("{:064x}", 2),
+            format!("{:0<64}", "deadbeef"),
+            format!("{:0<64}", "cafebabe"),
+        ]
+        .concat();
+        let decoded = abi_decode(&["bytes4[]"], &hex::decode(array).unwrap()).unwrap();
+        assert_eq!(
+            Vec::<FixedBytes<4>>::from_value(&decoded[0]).unwrap(),
+            vec![
+                FixedBytes::from(hex!("deadbeef")),
+                FixedBytes::from(hex!("cafebabe"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixed_bytes_round_trip() {
+        let encoded = [
+            format!("{:0<64}", "0102030405060708"),
+            format!("{:0<64}", "ff".repeat(31) + "01"),
+        ]
+        .concat();
+        let encoded = hex::decode(encoded).unwrap();
+        let type_strs = ["bytes8", "bytes32"];
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].eth_type(), "bytes8");
+        assert_eq!(
+            FixedBytes::<8>::from_value(&decoded[0]).unwrap(),
+            FixedBytes::from(hex!("0102030405060708"))
+        );
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+
+        // and through a dynamic array of each
+        let values = ValueBuilder::new()
+            .add_array(vec![FixedBytes::<8>::from(hex!("0102030405060708"))])
+            .add_array(vec![FixedBytes::<32>::from([0xab; 32])])
+            .build();
+        let type_strs = ["bytes8[]", "bytes32[]"];
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_empty_dynamic() {
+        let type_strs = ["string", "bytes"];
+        let values = ValueBuilder::new()
+            .add(String::new())
+            .add(Bytes::new())
+            .build();
+
+        // two offsets and two zero length words, no padding words
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            [
+                format!("{:064x}", 0x40),
+                format!("{:064x}", 0x60),
+                format!("{:064x}", 0),
+                format!("{:064x}", 0),
+            ]
+            .concat()
+        );
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        assert_eq!(String::from_value(&decoded[0]).unwrap(), "");
+        assert_eq!(Bytes::from_value(&decoded[1]).unwrap(), Bytes::new());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_decode_debug() {
+        let type_strs = ["uint256", "string"];
+        let values = ValueBuilder::new()
+            .add(U256::from(5))
+            .add(String::from("debug"))
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        let fields = abi_decode_debug(&type_strs, &encoded).unwrap();
+        assert!(!fields[0].was_dynamic);
+        assert_eq!(fields[0].head_word[31], 5);
+        assert_eq!(fields[0].followed_offset, None);
+
+        assert!(fields[1].was_dynamic);
+        assert_eq!(fields[1].head_word[31], 0x40);
+        assert_eq!(fields[1].followed_offset, Some(0x40));
+        assert_eq!(fields[1].value.to_string(), "debug");
+    }
+
+    #[test]
+    fn test_abi_decode_dirty_padding() {
+        let values = ValueBuilder::new().add(String::from("hi")).build();
+        let mut encoded = abi_encode(&["string"], &values).unwrap();
+        // the byte right after "hi" belongs to the padding
+        encoded[66] = 0x01;
+
+        let decoded = abi_decode(&["string"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "hi");
+        assert_eq!(
+            abi_decode_with_options(&["string"], &encoded, &DecodeOptions::strict()).unwrap_err(),
+            CodecError::DirtyPaddingBytes
+        );
+
+        encoded[66] = 0x00;
+        assert!(abi_decode_with_options(&["string"], &encoded, &DecodeOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn test_abi_decode_dirty_address_array_padding() {
+        let values = ValueBuilder::new()
+            .add_array(vec![Address::repeat_byte(0x01), Address::repeat_byte(0x02)])
+            .build();
+        let mut encoded = abi_encode(&["address[2]"], &values).unwrap();
+        assert!(
+            abi_decode_with_options(&["address[2]"], &encoded, &DecodeOptions::strict()).is_ok()
+        );
+
+        // first padding byte of the second element
+        encoded[32] = 0xee;
+        assert!(abi_decode(&["address[2]"], &encoded).is_ok());
+        assert_eq!(
+            abi_decode_with_options(&["address[2]"], &encoded, &DecodeOptions::strict())
+                .unwrap_err(),
+            CodecError::DirtyPaddingBytes
+        );
+
+        let negative = ValueBuilder::new().add(I8::MINUS_ONE).build();
+        let encoded = abi_encode(&["int8"], &negative).unwrap();
+        assert!(abi_decode_with_options(&["int8"], &encoded, &DecodeOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn test_abi_decode_truncated() {
+        let values = ValueBuilder::new()
+            .add(U256::from(1))
+            .add(String::from("truncated"))
+            .build();
+        let encoded = abi_encode(&["uint256", "string"], &values).unwrap();
+
+        assert_eq!(
+            abi_decode(&["uint256", "string"], &encoded[..40]).unwrap_err(),
+            CodecError::OutOfBounds(64, 40)
+        );
+        assert_eq!(
+            abi_decode(&["uint256", "string"], &encoded[..100]).unwrap_err(),
+            CodecError::OutOfBounds(41, 36)
+        );
+        assert_eq!(
+            abi_decode_with_signature("f(uint256)", &[0x01]).unwrap_err(),
+            CodecError::InvalidSelector
+        );
+    }
+
+    #[test]
+    fn test_read_usize() {
+        let mut word = [0u8; 32];
+        word[32 - size_of::<usize>()..].copy_from_slice(&usize::MAX.to_be_bytes());
+        assert_eq!(read_usize(&word), Ok(usize::MAX));
+
+        // one past the target's usize::MAX
+        let mut word = [0u8; 32];
+        word[31 - size_of::<usize>()] = 1;
+        assert_eq!(
+            read_usize(&word),
+            Err(CodecError::LengthOverflow(
+                U256::from(usize::MAX) + U256::from(1)
+            ))
+        );
+
+        let word = hex!("0000000000000000000000000000000000000000000000010000000000000020");
+        assert_eq!(
+            read_usize(&word),
+            Err(CodecError::LengthOverflow(U256::from_be_bytes(word)))
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_anonymous_log() {
+        let signature = "Moved(address,uint256,uint256)";
+        let from = Address::from([0x11; 20]);
+        let topics = vec![
+            abi_encode(&["address"], &ValueBuilder::new().add(from).build()).unwrap(),
+            abi_encode(
+                &["uint256"],
+                &ValueBuilder::new().add(U256::from(7)).build(),
+            )
+            .unwrap(),
+        ];
+        let data = abi_encode(
+            &["uint256"],
+            &ValueBuilder::new().add(U256::from(500)).build(),
+        )
+        .unwrap();
+
+        let values =
+            abi_decode_anonymous_log(signature, &[true, true, false], &topics, &data).unwrap();
+        assert_eq!(Address::from_value(&values[0]).unwrap(), from);
+        assert_eq!(U256::from_value(&values[1]).unwrap(), U256::from(7));
+        assert_eq!(U256::from_value(&values[2]).unwrap(), U256::from(500));
+
+        // a leading signature hash is one topic too many
+        let mut with_topic0 = vec![event_topic(signature).to_vec()];
+        with_topic0.extend(topics);
+        assert_eq!(
+            abi_decode_anonymous_log(signature, &[true, true, false], &with_topic0, &data)
+                .unwrap_err(),
+            CodecError::TopicCountMismatch {
+                expected: 2,
+                got: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let nested = |depth: usize| format!("{}uint256{}", "(".repeat(depth), ")".repeat(depth));
+        let encoded = U256::from(1).to_be_bytes::<32>();
+
+        let decoded = abi_decode(&[nested(DEFAULT_MAX_DEPTH).as_str()], &encoded).unwrap();
+        assert_eq!(
+            abi_encode(&[nested(DEFAULT_MAX_DEPTH).as_str()], &decoded).unwrap(),
+            encoded
+        );
+
+        assert_eq!(
+            abi_decode(&[nested(DEFAULT_MAX_DEPTH + 1).as_str()], &encoded).unwrap_err(),
+            CodecError::RecursionLimitExceeded(DEFAULT_MAX_DEPTH + 1)
+        );
+        assert_eq!(
+            abi_encode(&[nested(DEFAULT_MAX_DEPTH + 1).as_str()], &decoded).unwrap_err(),
+            CodecError::RecursionLimitExceeded(DEFAULT_MAX_DEPTH + 1)
+        );
+
+        // rejected up front rather than overflowing the stack
+        assert_eq!(
+            abi_decode(&[nested(10_000).as_str()], &encoded).unwrap_err(),
+            CodecError::RecursionLimitExceeded(10_000)
+        );
+
+        let options = DecodeOptions {
+            max_depth: 2,
+            ..Default::default()
+        };
+        let decoded = abi_decode_with_options(&[nested(2).as_str()], &encoded, &options).unwrap();
+        let Value::Collection(outer) = &decoded[0] else {
+            panic!("Expected tuple");
+        };
+        let Value::Collection(inner) = &outer[0] else {
+            panic!("Expected tuple");
+        };
+        assert_eq!(U256::from_value(&inner[0]).unwrap(), U256::from(1));
+        assert_eq!(
+            abi_decode_with_options(&[nested(3).as_str()], &encoded, &options).unwrap_err(),
+            CodecError::RecursionLimitExceeded(3)
+        );
+
+        // the encoder's limit can be raised as well as lowered
+        let deep = nested(DEFAULT_MAX_DEPTH + 1);
+        let one = create_value(U256::from(1), "uint256");
+        let value = (0..=DEFAULT_MAX_DEPTH).fold(one, |v, _| Value::new(vec![v]));
+        assert!(abi_encode(&[deep.as_str()], std::slice::from_ref(&value)).is_err());
+        assert_eq!(
+            abi_encode_with_max_depth(&[deep.as_str()], &[value], DEFAULT_MAX_DEPTH + 1).unwrap(),
+            encoded
+        );
+        assert_eq!(
+            abi_encode_with_max_depth(&[nested(2).as_str()], &decoded, 1).unwrap_err(),
+            CodecError::RecursionLimitExceeded(2)
+        );
+    }
+
+    #[test]
+    fn test_malformed_type_syntax() {
+        let encoded = [0u8; 64];
+        assert_eq!(
+            abi_decode(&["uint256[)]"], &encoded).unwrap_err(),
+            CodecError::InvalidTuple("uint256[)]".to_string())
+        );
+        assert_eq!(
+            abi_encode(&["(uint256])"], &[Value::new(Vec::new())]).unwrap_err(),
+            CodecError::InvalidArray("(uint256])".to_string())
+        );
+    }
+
+    #[test]
+    fn test_raw_on_unsupported() {
+        let encoded = [
+            format!("{:064x}", 7),
+            format!("{:064x}", 0xabcdef),
+            format!("{:064x}", 1),
+        ]
+        .concat();
+        let encoded = hex::decode(encoded).unwrap();
+        let type_strs = ["uint256", "price96x32", "bool"];
+
+        assert_eq!(
+            abi_decode(&type_strs, &encoded).unwrap_err(),
+            CodecError::UnsupportedType("price96x32".to_string())
+        );
+
+        let options = DecodeOptions {
+            raw_on_unsupported: true,
+            ..Default::default()
+        };
+        let decoded = abi_decode_with_options(&type_strs, &encoded, &options).unwrap();
+        assert_eq!(U256::from_value(&decoded[0]).unwrap(), U256::from(7));
+        assert_eq!(decoded[1].eth_type(), "raw");
+        assert_eq!(
+            FixedBytes::<32>::from_value(&decoded[1])
+                .unwrap()
+                .as_slice(),
+            &encoded[32..64]
+        );
+        assert!(bool::from_value(&decoded[2]).unwrap());
+    }
+
+    #[test]
+    fn test_mixed_static_and_dynamic_params() {
+        let address = Address::repeat_byte(0x22);
+        let type_strs = ["uint256", "(address,bytes)", "uint256[]", "string"];
+        let values = ValueBuilder::new()
+            .add(U256::from(42))
+            .add_value(Value::new(
+                ValueBuilder::new()
+                    .add(address)
+                    .add(Bytes::from(vec![0xab; 33]))
+                    .build(),
+            ))
+            .add_array(vec![U256::from(1), U256::from(2)])
+            .add(String::from("mixed"))
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        let words: Vec<U256> = encoded.chunks(32).map(|w| U256::from_be_slice(w)).collect();
+        // the static head, then offsets to each tail in order
+        assert_eq!(words[0], U256::from(42));
+        assert_eq!(words[1], U256::from(0x80));
+        // the tuple tail: address, offset, length and two words of bytes
+        assert_eq!(words[2], U256::from(0x80 + 5 * 32));
+        assert_eq!(words[3], U256::from(0x80 + 5 * 32 + 3 * 32));
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        assert_eq!(U256::from_value(&decoded[0]).unwrap(), U256::from(42));
+        let Value::Collection(tuple) = &decoded[1] else {
+            panic!("Expected tuple");
+        };
+        assert_eq!(Address::from_value(&tuple[0]).unwrap(), address);
+        assert_eq!(
+            Bytes::from_value(&tuple[1]).unwrap(),
+            Bytes::from(vec![0xab; 33])
+        );
+        assert_eq!(
+            Vec::<U256>::from_value(&decoded[2]).unwrap(),
+            vec![U256::from(1), U256::from(2)]
+        );
+        assert_eq!(String::from_value(&decoded[3]).unwrap(), "mixed");
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_read_param() {
+        let type_strs = ["uint256", "string", "address"];
+        let address = Address::repeat_byte(0x33);
+        let values = ValueBuilder::new()
+            .add(U256::from(9))
+            .add(String::from("middle"))
+            .add(address)
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        let middle = read_param(&type_strs, &encoded, 1).unwrap();
+        assert_eq!(String::from_value(&middle).unwrap(), "middle");
+        assert_eq!(
+            Address::from_value(&read_param(&type_strs, &encoded, 2).unwrap()).unwrap(),
+            address
+        );
+
+        // static params after a large one are found by their head size
+        let type_strs = ["uint256[3]", "(bool,uint8)", "uint16"];
+        let values = ValueBuilder::new()
+            .add_value(uint_array("uint256", vec![U256::from(1); 3]))
+            .add_value(Value::new(
+                ValueBuilder::new().add(true).add(U8::from(2)).build(),
+            ))
+            .add(U16::from(7))
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            U16::from_value(&read_param(&type_strs, &encoded, 2).unwrap()).unwrap(),
+            U16::from(7)
+        );
+
+        assert_eq!(
+            read_param(&type_strs, &encoded, 3).unwrap_err(),
+            CodecError::LengthsMismatch(3, 4)
+        );
+        assert_eq!(
+            read_param(&type_strs, &encoded, usize::MAX).unwrap_err(),
+            CodecError::LengthsMismatch(3, usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_lenient() {
+        let type_strs = ["uint256", "bytes"];
+        let values = ValueBuilder::new()
+            .add(U256::from(3))
+            .add(Bytes::from(vec![0xcd; 40]))
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        let (decoded, was_truncated) = abi_decode_lenient(&type_strs, &encoded).unwrap();
+        assert!(!was_truncated);
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+
+        // head, length word and 20 of the 40 bytes
+        let truncated = &encoded[..32 * 3 + 20];
+        assert!(abi_decode(&type_strs, truncated).is_err());
+        let (decoded, was_truncated) = abi_decode_lenient(&type_strs, truncated).unwrap();
+        assert!(was_truncated);
+        assert_eq!(U256::from_value(&decoded[0]).unwrap(), U256::from(3));
+        assert_eq!(
+            Bytes::from_value(&decoded[1]).unwrap(),
+            Bytes::from(vec![0xcd; 20])
+        );
+
+        // a head cut short is still an error
+        assert!(abi_decode_lenient(&type_strs, &encoded[..40]).is_err());
+    }
+
+    #[test]
+    fn test_abi_decode_lenient_split_character() {
+        // "aé" is declared as 3 bytes but cut after the first byte of `é`
+        let values = ValueBuilder::new().add(String::from("aé")).build();
+        let encoded = abi_encode(&["string"], &values).unwrap();
+        let truncated = &encoded[..32 * 2 + 2];
+
+        let (decoded, was_truncated) = abi_decode_lenient(&["string"], truncated).unwrap();
+        assert!(was_truncated);
+        assert_eq!(decoded[0].to_string(), "a");
+
+        // bytes that were never valid UTF-8 are still rejected
+        let mut invalid = encoded.clone();
+        invalid[65] = 0xff;
+        assert!(abi_decode_lenient(&["string"], &invalid[..32 * 2 + 2]).is_err());
+    }
+
+    #[test]
+    fn test_negative_int_to_string() {
+        let encoded = I256::try_from(-42).unwrap().to_be_bytes::<32>();
+        let decoded = abi_decode(&["int256"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "-42");
+
+        let decoded = abi_decode(&["int8"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "-42");
+    }
+
+    #[test]
+    fn test_is_valid_calldata() {
+        let signature = "setName(uint256,string)";
+        let values = ValueBuilder::new()
+            .add(U256::from(1))
+            .add(String::from("alice"))
+            .build();
+        let calldata = abi_encode_with_singature(signature, &values).unwrap();
+        assert!(is_valid_calldata(signature, &calldata));
+
+        let mut wrong_selector = calldata.clone();
+        wrong_selector[0] ^= 0xff;
+        assert!(!is_valid_calldata(signature, &wrong_selector));
+
+        let mut trailing = calldata.clone();
+        trailing.extend([0u8; 32]);
+        assert!(!is_valid_calldata(signature, &trailing));
+
+        assert!(!is_valid_calldata(
+            signature,
+            &calldata[..calldata.len() - 1]
+        ));
+        assert!(!is_valid_calldata(signature, &calldata[..3]));
+    }
+
+    #[test]
+    fn test_tuple_array_with_nested_string_arrays() {
+        let type_strs = ["((uint256,string[])[],address)"];
+        let entry = |n: u64, strings: &[&str]| {
+            Value::new(vec![
+                create_value(U256::from(n), "uint256"),
+                string_array(strings.iter().map(|s| s.to_string()).collect()),
+            ])
+        };
+        let values = vec![Value::new(vec![
+            Value::new(vec![
+                entry(1, &["a", "bc"]),
+                entry(2, &[]),
+                entry(3, &["def"]),
+            ]),
+            create_value(Address::repeat_byte(0x66), "address"),
+        ])];
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        let words: Vec<U256> = encoded.chunks(32).map(|w| U256::from_be_slice(w)).collect();
+        // outer offset, then the tuple head: array offset and the address
+        assert_eq!(words[0], U256::from(0x20));
+        assert_eq!(words[1], U256::from(0x40));
+        assert_eq!(words[3], U256::from(3));
+        // element offsets are relative to the start of the elements; the
+        // first element is its two-word head plus a seven-word string[] tail
+        assert_eq!(words[4], U256::from(0x60));
+        assert_eq!(words[5], U256::from(0x60 + 9 * 32));
+        assert_eq!(words[6], U256::from(0x60 + 9 * 32 + 3 * 32));
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+        let Value::Collection(outer) = &decoded[0] else {
+            panic!("Expected tuple");
+        };
+        let Value::Collection(entries) = &outer[0] else {
+            panic!("Expected array");
+        };
+        let Value::Collection(last) = &entries[2] else {
+            panic!("Expected tuple");
+        };
+        assert_eq!(
+            Vec::<String>::from_value(&last[1]).unwrap(),
+            vec!["def".to_string()]
+        );
+        assert_eq!(
+            Address::from_value(&outer[1]).unwrap(),
+            Address::repeat_byte(0x66)
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_log_topic_count() {
+        let signature = "Approval(address,address,uint256)";
+        let topic = |byte: u8| {
+            let mut topic = vec![0u8; 32];
+            topic[31] = byte;
+            topic
+        };
+        let data = U256::from(1).to_be_bytes::<32>();
+
+        // three indexed params need four topics
+        let topics = vec![event_topic(signature).to_vec(), topic(1), topic(2)];
+        assert_eq!(
+            abi_decode_log(signature, &[true, true, true], &topics, &[]).unwrap_err(),
+            CodecError::TopicCountMismatch {
+                expected: 4,
+                got: 3
+            }
+        );
+        assert_eq!(
+            abi_decode_log(signature, &[true, true, true], &[], &[]).unwrap_err(),
+            CodecError::TopicCountMismatch {
+                expected: 4,
+                got: 0
+            }
+        );
+        assert!(abi_decode_log(signature, &[true, true, false], &topics, &data).is_ok());
+    }
+
+    #[test]
+    fn test_parse_topics() {
+        let topics = parse_topics(&[
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            "0x000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .unwrap();
+        assert_eq!(topics.len(), 3);
+        assert_eq!(topics[0], event_topic("Transfer(address,address,uint256)"));
+        assert_eq!(topics[2][31], 1);
+
+        let values = abi_decode_log(
+            "Transfer(address,address,uint256)",
+            &[true, true, false],
+            &[topics[0].clone(), topics[1].clone(), topics[2].clone()],
+            &U256::from(10).to_be_bytes::<32>(),
+        )
+        .unwrap();
+        assert_eq!(U256::from_value(&values[2]).unwrap(), U256::from(10));
+
+        assert_eq!(
+            parse_topics(&["0xddf252ad"]).unwrap_err(),
+            CodecError::InvalidValueLength(4)
+        );
+        assert!(parse_topics(&["0xzz"]).is_err());
+    }
+
+    #[test]
+    fn test_abi_decode_owned() {
+        let type_strs: Vec<String> = "uint256,string,address[]"
+            .split(',')
+            .map(String::from)
+            .collect();
+        let values = ValueBuilder::new()
+            .add(U256::from(8))
+            .add(String::from("owned"))
+            .add_array(vec![Address::repeat_byte(0x01)])
+            .build();
+
+        let encoded = crate::encode::abi_encode_owned(&type_strs, &values).unwrap();
+        assert_eq!(
+            encoded,
+            abi_encode(&["uint256", "string", "address[]"], &values).unwrap()
+        );
+
+        let decoded = abi_decode_owned(&type_strs, &encoded).unwrap();
+        assert_eq!(String::from_value(&decoded[1]).unwrap(), "owned");
+        assert_eq!(
+            Vec::<Address>::from_value(&decoded[2]).unwrap(),
+            vec![Address::repeat_byte(0x01)]
+        );
+    }
+
+    #[test]
+    fn test_decode_through_proxy() {
+        let impl_signatures = [
+            "upgradeTo(address)",
+            "transfer(address,uint256)",
+            "approve(address,uint256)",
+        ];
+        let to = Address::repeat_byte(0x88);
+        let calldata = abi_encode_with_singature(
+            "transfer(address,uint256)",
+            &ValueBuilder::new().add(to).add(U256::from(99)).build(),
+        )
+        .unwrap();
+
+        let (signature, values) = decode_through_proxy(&calldata, &impl_signatures).unwrap();
+        assert_eq!(signature, "transfer(address,uint256)");
+        assert_eq!(Address::from_value(&values[0]).unwrap(), to);
+        assert_eq!(U256::from_value(&values[1]).unwrap(), U256::from(99));
+
+        assert!(decode_through_proxy(&calldata, &impl_signatures[..1]).is_none());
+    }
+
+    #[test]
+    fn test_tuple_array_nesting() {
+        let members = || uint_array("uint256", vec![U256::from(1), U256::from(2)]);
+        let cases = [
+            ("(uint256[])", Value::new(vec![members()])),
+            (
+                "(uint256)[]",
+                Value::new(vec![Value::new(vec![create_value(
+                    U256::from(1),
+                    "uint256",
+                )])]),
+            ),
+            (
+                "(uint256[])[]",
+                Value::new(vec![
+                    Value::new(vec![members()]),
+                    Value::new(vec![members()]),
+                ]),
+            ),
+        ];
+
+        for (type_str, value) in cases {
+            let encoded = abi_encode(&[type_str], &[value]).unwrap();
+            let decoded =
+                abi_decode_with_options(&[type_str], &encoded, &DecodeOptions::strict()).unwrap();
+            assert_eq!(abi_encode(&[type_str], &decoded).unwrap(), encoded);
+        }
+    }
+
+    #[test]
+    fn test_narrow_uint_round_trip() {
+        let encoded = [format!("{:064x}", 0x7f), format!("{:064x}", 0xff)].concat();
+        let encoded = hex::decode(encoded).unwrap();
+        let type_strs = ["(uint8,uint8)"];
+
+        let decoded = abi_decode(&type_strs, &encoded).unwrap();
+        let Value::Collection(members) = &decoded[0] else {
+            panic!("Expected tuple");
+        };
+        assert_eq!(members[0].eth_type(), "uint8");
+        assert_eq!(U8::from_value(&members[1]).unwrap(), U8::from(0xff));
+
+        // integers keep their value in the low byte, unlike bytes1
+        let reencoded = abi_encode(&type_strs, &decoded).unwrap();
+        assert_eq!(reencoded, encoded);
+        assert_eq!(reencoded[31], 0x7f);
+        assert!(reencoded[..31].iter().all(|b| *b == 0));
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_abi_decode_named_map() {
+        let signature = "function swap(address to, uint256 amount, bool, uint256 amount) external";
+        let calldata = abi_encode_with_singature(
+            "swap(address,uint256,bool,uint256)",
+            &ValueBuilder::new()
+                .add(Address::repeat_byte(0x99))
+                .add(U256::from(1))
+                .add(true)
+                .add(U256::from(2))
+                .build(),
+        )
+        .unwrap();
+
+        let map = abi_decode_named_map(signature, &calldata).unwrap();
+        assert_eq!(
+            map.keys().collect::<Vec<&String>>(),
+            vec!["to", "amount", "2", "amount_3"]
+        );
+        assert_eq!(U256::from_value(&map["amount_3"]).unwrap(), U256::from(2));
+        assert_eq!(
+            Address::from_value(&map["to"]).unwrap(),
+            Address::repeat_byte(0x99)
+        );
+
+        // `amount_2` is both a real name and the key the second `amount` gets
+        let calldata = abi_encode_with_singature(
+            "swap(uint256,uint256,uint256)",
+            &ValueBuilder::new()
+                .add(U256::from(1))
+                .add(U256::from(2))
+                .add(U256::from(3))
+                .build(),
+        )
+        .unwrap();
+        assert!(matches!(
+            abi_decode_named_map(
+                "swap(uint256 amount, uint256 amount_2, uint256 amount)",
+                &calldata
+            ),
+            Err(CodecError::InvalidFunctionSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_unaligned_offset() {
+        // offset 33, then a length of 1 straddling the word boundary
+        let mut encoded = U256::from(33).to_be_bytes::<32>().to_vec();
+        encoded.push(0);
+        encoded.extend(U256::from(1).to_be_bytes::<32>());
+        encoded.extend([0x61; 32]);
+
+        let decoded = abi_decode(&["string"], &encoded).unwrap();
+        assert_eq!(String::from_value(&decoded[0]).unwrap(), "a");
+        assert_eq!(
+            abi_decode_with_options(&["string"], &encoded, &DecodeOptions::strict()).unwrap_err(),
+            CodecError::MalformedOffsets("offset 33 is not a multiple of 32".to_string())
+        );
+    }
+
+    #[test]
+    fn test_nested_tuple_shared_offset_base() {
+        let type_strs = ["uint256", "(bytes,bytes)"];
+        let values = ValueBuilder::new()
+            .add(U256::from(5))
+            .add_value(Value::new(
+                ValueBuilder::new()
+                    .add(Bytes::from(vec![0x01; 40]))
+                    .add(Bytes::from(vec![0x02; 3]))
+                    .build(),
+            ))
+            .build();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+
+        // the tuple starts at 0x40; its offsets are relative to that start
+        let tuple = &encoded[0x40..];
+        assert_eq!(U256::from_be_slice(&tuple[..32]), U256::from(0x40));
+        assert_eq!(
+            U256::from_be_slice(&tuple[32..64]),
+            U256::from(0x40 + 32 + 64)
+        );
+
+        let decoded =
+            abi_decode_with_options(&type_strs, &encoded, &DecodeOptions::strict()).unwrap();
+        let Value::Collection(members) = &decoded[1] else {
+            panic!("Expected tuple");
+        };
+        assert_eq!(
+            Bytes::from_value(&members[0]).unwrap(),
+            Bytes::from(vec![0x01; 40])
+        );
+        assert_eq!(
+            Bytes::from_value(&members[1]).unwrap(),
+            Bytes::from(vec![0x02; 3])
+        );
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_work_limit() {
+        // 64 heads of a bytes[] all pointing at one 2048-byte tail
+        let (heads, words) = (64, 64);
+        let mut encoded = vec![U256::from(0x20), U256::from(heads)];
+        encoded.extend(vec![U256::from(heads * 32); heads]);
+        encoded.push(U256::from(words * 32));
+        encoded.extend(vec![U256::MAX; words]);
+        let encoded: Vec<u8> = encoded.iter().flat_map(|w| w.to_be_bytes::<32>()).collect();
+
+        let limit = (encoded.len() / 32 + 1) * 8;
+        assert_eq!(
+            abi_decode(&["bytes[]"], &encoded).unwrap_err(),
+            CodecError::WorkLimitExceeded(limit)
+        );
+
+        // the same values laid out canonically decode fine
+        let mut builder = ValueBuilder::new();
+        builder.add_array(vec![Bytes::from(vec![0xffu8; words * 32]); heads]);
+        let canonical = abi_encode(&["bytes[]"], &builder.build()).unwrap();
+        assert!(abi_decode(&["bytes[]"], &canonical).is_ok());
+
+        // zero-sized elements can't be used to spin either
+        let mut empty_tuples = U256::from(0x20).to_be_bytes::<32>().to_vec();
+        empty_tuples.extend(U256::from(u32::MAX).to_be_bytes::<32>());
+        assert_eq!(
+            abi_decode(&["()[]"], &empty_tuples).unwrap_err(),
+            CodecError::WorkLimitExceeded(3 * 8)
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_array_raw() {
+        let values = vec![uint_array(
+            "uint256",
+            vec![U256::from(1), U256::from(2), U256::from(3)],
+        )];
+        let encoded = abi_encode(&["uint256[]"], &values).unwrap();
+
+        let words = abi_decode_array_raw("uint256", &encoded[32..]).unwrap();
+        assert_eq!(
+            words,
+            vec![
+                U256::from(1).to_be_bytes::<32>(),
+                U256::from(2).to_be_bytes::<32>(),
+                U256::from(3).to_be_bytes::<32>(),
+            ]
+        );
+
+        // dynamic elements give their offsets, relative to the first head
+        let values = vec![string_array(vec!["a".to_string(), "b".to_string()])];
+        let encoded = abi_encode(&["string[]"], &values).unwrap();
+        let words = abi_decode_array_raw("string", &encoded[32..]).unwrap();
+        assert_eq!(
+            words,
+            vec![
+                U256::from(0x40).to_be_bytes::<32>(),
+                U256::from(0x80).to_be_bytes::<32>(),
+            ]
+        );
+
+        assert_eq!(
+            abi_decode_array_raw("uint256", &encoded[32..64]),
+            Err(CodecError::OutOfBounds(64, 0))
+        );
+        assert_eq!(
+            abi_decode_array_raw("(uint256,bool)", &encoded[32..]),
+            Err(CodecError::UnsupportedType("(uint256,bool)".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_abi_decode_fixed_array_raw() {
+        let values = vec![uint_array(
+            "uint256",
+            vec![U256::from(1), U256::from(2), U256::from(3)],
+        )];
+        let encoded = abi_encode(&["uint256[3]"], &values).unwrap();
+        assert_eq!(encoded.len(), 96);
+
+        let words = abi_decode_fixed_array_raw("uint256", 3, &encoded).unwrap();
+        assert_eq!(
+            words,
+            vec![
+                U256::from(1).to_be_bytes::<32>(),
+                U256::from(2).to_be_bytes::<32>(),
+                U256::from(3).to_be_bytes::<32>(),
+            ]
+        );
+        assert_eq!(
+            abi_decode_fixed_array_raw("uint256", 4, &encoded),
+            Err(CodecError::OutOfBounds(128, 96))
+        );
     }
 }
@@ -1,10 +1,16 @@
 use crate::codec::traits::EncodeCodec;
-use crate::codec::types::Value;
+use crate::codec::types::{Value, is_homogeneous};
 use crate::codec::utils::{get_collection_i, pad_left, pad_right};
-use crate::common::{check_type_and_value, is_array, is_dynamic, is_tuple, split_parameter_types};
+use crate::common::{
+    DEFAULT_MAX_DEPTH, check_depth, check_type_and_value, function_head, get_array_element_type,
+    get_bytes_from_type, get_parameter_types, is_array, is_dynamic, is_fixed_bytes, is_signed,
+    is_tuple, normalize_type_case, validate_type_syntax,
+};
 use crate::errors::CodecError;
 use alloy_primitives::aliases::U256;
+use alloy_primitives::hex;
 use alloy_primitives::utils::keccak256;
+use std::io::Write;
 
 #[derive(Debug)]
 struct DynamicPlaceholder {
@@ -14,39 +20,81 @@ struct DynamicPlaceholder {
 
 pub fn abi_encode_with_selector(
     selector: &[u8; 4],
-    type_strs: &Vec<&str>,
-    values: &Vec<Value>,
+    type_strs: &[&str],
+    values: &[Value],
 ) -> Result<Vec<u8>, CodecError> {
-    let encoded = abi_encode(&type_strs, values)?;
+    let encoded = abi_encode(type_strs, values)?;
 
-    Ok(selector
-        .to_vec()
-        .into_iter()
-        .chain(encoded.into_iter())
-        .collect())
+    Ok(selector.iter().copied().chain(encoded).collect())
+}
+
+// calldata from arguments that were encoded elsewhere: the 4-byte selector
+// of the signature followed by the arguments as they are
+pub fn prepend_selector(signature: &str, encoded_args: &[u8]) -> Result<Vec<u8>, CodecError> {
+    let selector = try_abi_encode_selector_array(signature)?;
+
+    Ok(selector.iter().chain(encoded_args).copied().collect())
 }
 
 pub fn abi_encode_selector(signature: &str) -> Result<Vec<u8>, CodecError> {
-    let selector = keccak256(signature.as_bytes());
+    Ok(try_abi_encode_selector_array(signature)?.to_vec())
+}
 
-    Ok(selector.to_bytes_vec()[0..4].to_vec())
+// the selector on the stack, for comparisons that need no allocation. Meant for
+// signatures already known to be valid: one without a parameter list is hashed
+// as given instead of being rejected
+pub fn abi_encode_selector_array(signature: &str) -> [u8; 4] {
+    selector_from_preimage(function_head(signature).unwrap_or(signature).as_bytes())
 }
 
-pub fn abi_encode_with_singature(
-    signature: &str,
-    values: &Vec<Value>,
-) -> Result<Vec<u8>, CodecError> {
+// like `abi_encode_selector_array`, but rejects malformed signatures
+pub fn try_abi_encode_selector_array(signature: &str) -> Result<[u8; 4], CodecError> {
+    error_selector(function_head(signature)?)
+}
+
+// custom errors are selected exactly like functions
+pub fn error_selector(signature: &str) -> Result<[u8; 4], CodecError> {
+    Ok(selector_from_preimage(signature.as_bytes()))
+}
+
+// ERC-165 interface id: the XOR of the selectors of every function in it
+pub fn compute_interface_id(signatures: &[&str]) -> Result<[u8; 4], CodecError> {
+    let mut interface_id = [0u8; 4];
+    for signature in signatures {
+        let selector = try_abi_encode_selector_array(signature)?;
+        for (id_byte, selector_byte) in interface_id.iter_mut().zip(selector) {
+            *id_byte ^= selector_byte;
+        }
+    }
+
+    Ok(interface_id)
+}
+
+// the selector of an arbitrary preimage, used as-is without any normalization
+pub fn selector_from_preimage(preimage: &[u8]) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak256(preimage)[..4]);
+
+    selector
+}
+
+pub fn event_topic(signature: &str) -> [u8; 32] {
+    signature_hash(signature)
+}
+
+fn signature_hash(signature: &str) -> [u8; 32] {
+    keccak256(signature.as_bytes()).0
+}
+
+pub fn abi_encode_with_singature(signature: &str, values: &[Value]) -> Result<Vec<u8>, CodecError> {
     let selector = abi_encode_selector(signature)?;
-    let type_strs = split_parameter_types(signature);
+    let type_strs = get_parameter_types(signature)?;
     let encoded = abi_encode(&type_strs, values)?;
 
-    Ok(selector.into_iter().chain(encoded.into_iter()).collect())
+    Ok(selector.into_iter().chain(encoded).collect())
 }
 
-pub fn abi_encode_packed(
-    type_strs: &Vec<&str>,
-    values: &Vec<Value>,
-) -> Result<Vec<u8>, CodecError> {
+pub fn abi_encode_packed(type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
     if type_strs.len() != values.len() {
         return Err(CodecError::LengthsMismatch(type_strs.len(), values.len()));
     }
@@ -72,7 +120,215 @@ pub fn abi_encode_packed(
     Ok(encoded)
 }
 
-pub fn abi_encode(type_strs: &Vec<&str>, values: &Vec<Value>) -> Result<Vec<u8>, CodecError> {
+// packs each value into exactly the given number of bytes, like
+// `abi.encodePacked(uint48(x))`; integers must fit the narrower width, other
+// values must already have it
+pub fn abi_encode_packed_widths(entries: &[(Value, usize)]) -> Result<Vec<u8>, CodecError> {
+    let mut encoded = Vec::new();
+    for (value, width) in entries {
+        let value_type = value.eth_type();
+        let kind = ["uint", "int"]
+            .into_iter()
+            .find(|kind| value_type.starts_with(kind));
+        let encoded_value = match kind {
+            Some(kind) if (1..=32).contains(width) => {
+                fit_integer(&format!("{}{}", kind, width * 8), value)?
+            }
+            None if !value.is_collection() && value.bytes_length() == *width => {
+                value.to_bytes_vec()
+            }
+            _ => {
+                return Err(CodecError::InvalidTypeAndValue(
+                    value_type,
+                    format!(
+                        "{} cannot be packed into {} bytes",
+                        value.to_string(),
+                        width
+                    ),
+                ));
+            }
+        };
+
+        encoded.extend(encoded_value);
+    }
+
+    Ok(encoded)
+}
+
+pub fn verify_packed_commitment(
+    type_strs: &[&str],
+    values: &[Value],
+    expected_hash: [u8; 32],
+) -> Result<bool, CodecError> {
+    let encoded = abi_encode_packed(type_strs, values)?;
+
+    Ok(keccak256(&encoded) == expected_hash)
+}
+
+pub fn abi_encode(type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    abi_encode_with_max_depth(type_strs, values, DEFAULT_MAX_DEPTH)
+}
+
+// `abi_encode` rejecting types nested deeper than `max_depth` instead of
+// `DEFAULT_MAX_DEPTH`
+pub fn abi_encode_with_max_depth(
+    type_strs: &[&str],
+    values: &[Value],
+    max_depth: usize,
+) -> Result<Vec<u8>, CodecError> {
+    check_types(type_strs, max_depth)?;
+    encode_params(type_strs, values)
+}
+
+// syntax and depth are checked once for the whole type list; the recursive
+// encoders below only ever see its members
+fn check_types(type_strs: &[&str], max_depth: usize) -> Result<(), CodecError> {
+    for type_str in type_strs {
+        validate_type_syntax(type_str)?;
+    }
+
+    check_depth(type_strs, max_depth)
+}
+
+fn encode_params(type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    let (mut header, footer) = encode_head_tail(type_strs, values)?;
+    header.extend(footer);
+
+    Ok(header)
+}
+
+// for type lists built at runtime as owned strings
+pub fn abi_encode_owned(type_strs: &[String], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    let type_strs: Vec<&str> = type_strs.iter().map(|t| t.as_str()).collect();
+
+    abi_encode(&type_strs, values)
+}
+
+// Encodes an ordered list of named fields as a single tuple parameter; the
+// names only document the struct and each field is typed by its tag. A
+// collection can't say whether it is an array or a tuple, so structs with
+// collection fields go through `abi_encode_struct_with_types`.
+pub fn abi_encode_struct(fields: &[(&str, Value)]) -> Result<Vec<u8>, CodecError> {
+    abi_encode_struct_with_types(fields, &[])
+}
+
+// `abi_encode_struct` with the collection fields typed by `collection_types`,
+// one per collection in field order
+pub fn abi_encode_struct_with_types(
+    fields: &[(&str, Value)],
+    collection_types: &[&str],
+) -> Result<Vec<u8>, CodecError> {
+    let values: Vec<Value> = fields.iter().map(|(_, value)| value.clone()).collect();
+    let member_types = infer_types(&values, collection_types)?;
+    let tuple_type = format!("({})", member_types.join(","));
+
+    abi_encode(&[tuple_type.as_str()], &[Value::new(values)])
+}
+
+// Encodes calldata for `name` with the parameter types taken from the values
+// themselves. Only the tags are seen, so a value must already carry the width
+// the function expects (a U256 tagged uint256 never matches a uint8 param).
+// A collection can be an array or a tuple alike, so calls with collection
+// arguments go through `abi_encode_infer_with_types`.
+pub fn abi_encode_infer(name: &str, values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    abi_encode_infer_with_types(name, values, &[])
+}
+
+// `abi_encode_infer` with the collection arguments typed by
+// `collection_types`, one per collection in order
+pub fn abi_encode_infer_with_types(
+    name: &str,
+    values: &[Value],
+    collection_types: &[&str],
+) -> Result<Vec<u8>, CodecError> {
+    let type_strs = infer_types(values, collection_types)?;
+    let signature = format!("{}({})", name, type_strs.join(","));
+
+    abi_encode_with_singature(&signature, values)
+}
+
+// types of a value list: scalars by their tags, collections from the given
+// types, one per collection in order
+fn infer_types(values: &[Value], collection_types: &[&str]) -> Result<Vec<String>, CodecError> {
+    let collections = values.iter().filter(|v| v.is_collection()).count();
+    if collections != collection_types.len() {
+        return Err(CodecError::LengthsMismatch(
+            collections,
+            collection_types.len(),
+        ));
+    }
+
+    let mut collection_types = collection_types.iter();
+    Ok(values
+        .iter()
+        .map(|value| match value {
+            Value::Single(_, type_str) => type_str.clone(),
+            Value::Collection(_) => collection_types.next().unwrap().to_string(),
+        })
+        .collect())
+}
+
+pub fn abi_encode_normalized(type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    let type_strs: Vec<String> = type_strs.iter().map(|t| normalize_type_case(t)).collect();
+    let type_strs: Vec<&str> = type_strs.iter().map(|t| t.as_str()).collect();
+
+    abi_encode(&type_strs, values)
+}
+
+pub fn abi_encode_to_writer<W: Write>(
+    writer: &mut W,
+    type_strs: &[&str],
+    values: &[Value],
+) -> Result<usize, CodecError> {
+    let (header, footer) = abi_encode_head_tail(type_strs, values)?;
+    writer
+        .write_all(&header)
+        .and_then(|_| writer.write_all(&footer))
+        .map_err(|e| CodecError::WriteError(e.to_string()))?;
+
+    Ok(header.len() + footer.len())
+}
+
+// one 32-byte word per line with its offset; a leading 4-byte selector is
+// shown on its own line and offsets then start after it
+pub fn format_calldata(bytes: &[u8]) -> String {
+    let mut lines = Vec::new();
+    let mut words = bytes;
+    if bytes.len() % 32 == 4 {
+        lines.push(format!("selector: 0x{}", hex::encode(&bytes[..4])));
+        words = &bytes[4..];
+    }
+
+    for (i, word) in words.chunks(32).enumerate() {
+        lines.push(format!("0x{:02x}: {}", i * 32, hex::encode(word)));
+    }
+
+    lines.join("\n")
+}
+
+// zero bytes, non-zero bytes and their calldata gas: 4 per zero byte and
+// 16 per non-zero byte (EIP-2028)
+pub fn calldata_gas_stats(data: &[u8]) -> (usize, usize, u64) {
+    let zero = data.iter().filter(|b| **b == 0).count();
+    let nonzero = data.len() - zero;
+
+    (zero, nonzero, zero as u64 * 4 + nonzero as u64 * 16)
+}
+
+// the head (static values and offsets) and the dynamic tail as separate
+// buffers; offsets already account for the full head length
+pub fn abi_encode_head_tail(
+    type_strs: &[&str],
+    values: &[Value],
+) -> Result<(Vec<u8>, Vec<u8>), CodecError> {
+    check_types(type_strs, DEFAULT_MAX_DEPTH)?;
+    encode_head_tail(type_strs, values)
+}
+
+fn encode_head_tail(
+    type_strs: &[&str],
+    values: &[Value],
+) -> Result<(Vec<u8>, Vec<u8>), CodecError> {
     if type_strs.len() != values.len() {
         return Err(CodecError::LengthsMismatch(type_strs.len(), values.len()));
     }
@@ -87,17 +343,10 @@ pub fn abi_encode(type_strs: &Vec<&str>, values: &Vec<Value>) -> Result<Vec<u8>,
 
         let encoded_value = if is_array_type {
             let value = get_collection_i(values, i);
-            encode_array(
-                type_str,
-                &value,
-                size,
-                is_dynamic_type,
-                is_tuple_type,
-                &tuple_types,
-            )?
+            encode_array(type_str, &value, size, is_tuple_type, &tuple_types)?
         } else if is_tuple_type {
             let value = get_collection_i(values, i);
-            abi_encode(&tuple_types, &value)?
+            encode_params(&tuple_types, &value)?
         } else {
             encode(type_str, value, is_dynamic_type)?
         };
@@ -105,14 +354,13 @@ pub fn abi_encode(type_strs: &Vec<&str>, values: &Vec<Value>) -> Result<Vec<u8>,
         if is_dynamic_type {
             let placeholder = pad_right(Vec::new(), 32);
             dyn_header_placeholder.push(DynamicPlaceholder {
-                header_offset: i * 32,
+                header_offset: header.len(),
                 footer_offset: footer.len(),
             });
 
             footer.extend(encoded_value);
             header.extend(placeholder);
         } else {
-            let encoded_value = encode(type_str, value, is_dynamic_type)?;
             header.extend(encoded_value);
         };
     }
@@ -122,9 +370,8 @@ pub fn abi_encode(type_strs: &Vec<&str>, values: &Vec<Value>) -> Result<Vec<u8>,
         let value = U256::from(header.len() + placeholder.footer_offset);
         header[offset..offset + value.bytes_length()].copy_from_slice(&value.to_bytes_vec());
     }
-    header.extend(footer);
 
-    Ok(header)
+    Ok((header, footer))
 }
 
 fn encode(type_str: &str, value: &Value, is_dynamic_type: bool) -> Result<Vec<u8>, CodecError> {
@@ -132,15 +379,22 @@ fn encode(type_str: &str, value: &Value, is_dynamic_type: bool) -> Result<Vec<u8
 
     if is_dynamic_type {
         let length = encoded.len();
-        encoded = pad_right(encoded, 32);
+        // dynamic data is right-padded up to the next word boundary
+        encoded = pad_right(encoded, length.div_ceil(32) * 32);
         let length = U256::from(length);
-        encoded = length
-            .to_bytes_vec()
-            .into_iter()
-            .chain(encoded.into_iter())
-            .collect();
+        encoded = length.to_bytes_vec().into_iter().chain(encoded).collect();
     } else {
-        encoded = pad_left(encoded, 32);
+        // negative signed numbers are sign-extended to the full word
+        if is_signed(type_str) && encoded.first().is_some_and(|b| b & 0x80 != 0) {
+            let mut extended = vec![0xff; 32 - encoded.len()];
+            extended.extend(encoded);
+            encoded = extended;
+        } else if is_fixed_bytes(type_str) {
+            // fixed-size bytes occupy the high-order bytes of the word
+            encoded = pad_right(encoded, 32);
+        } else {
+            encoded = pad_left(encoded, 32);
+        }
     }
 
     Ok(encoded)
@@ -148,11 +402,10 @@ fn encode(type_str: &str, value: &Value, is_dynamic_type: bool) -> Result<Vec<u8
 
 fn encode_array(
     arr_type_str: &str,
-    values: &Vec<Value>,
+    values: &[Value],
     size: usize,
-    is_dynamic_type: bool,
     is_tuple_type: bool,
-    tuple_types: &Vec<&str>,
+    tuple_types: &[&str],
 ) -> Result<Vec<u8>, CodecError> {
     if size != 0 && size != values.len() {
         return Err(CodecError::InvalidTypeAndValue(
@@ -164,7 +417,21 @@ fn encode_array(
             ),
         ));
     }
+    if !is_homogeneous(values) {
+        return Err(CodecError::InvalidTypeAndValue(
+            arr_type_str.to_string(),
+            format!(
+                "array elements differ in type: {}",
+                values
+                    .iter()
+                    .map(|v| v.eth_type())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+    }
     let type_str = arr_type_str.split("[").next().unwrap();
+    let is_dynamic_element = is_dynamic(get_array_element_type(arr_type_str));
 
     let mut header: Vec<u8> = Vec::new();
 
@@ -173,22 +440,21 @@ fn encode_array(
     for (i, value) in values.iter().enumerate() {
         let encoded_value = if is_tuple_type {
             let value = get_collection_i(values, i);
-            abi_encode(tuple_types, &value)?
+            encode_params(tuple_types, &value)?
         } else {
-            encode(type_str, value, is_dynamic_type)?
+            encode(type_str, value, is_dynamic_element)?
         };
 
-        if is_dynamic_type {
+        if is_dynamic_element {
             let placeholder = pad_right(Vec::new(), 32);
             dyn_header_placeholder.push(DynamicPlaceholder {
-                header_offset: i * 32,
+                header_offset: header.len(),
                 footer_offset: footer.len(),
             });
 
             footer.extend(encoded_value);
             header.extend(placeholder);
         } else {
-            let encoded_value = encode(type_str, value, is_dynamic_type)?;
             header.extend(encoded_value);
         };
     }
@@ -205,7 +471,7 @@ fn encode_array(
         header = array_length
             .to_bytes_vec()
             .into_iter()
-            .chain(header.into_iter())
+            .chain(header)
             .collect();
     }
 
@@ -214,23 +480,70 @@ fn encode_array(
 
 fn encode_packed(type_str: &str, value: &Value) -> Result<Vec<u8>, CodecError> {
     if !check_type_and_value(type_str, value) {
+        return fit_integer(type_str, value);
+    }
+
+    Ok(value.to_bytes_vec())
+}
+
+// integers may be declared narrower or wider than the value's own type, as
+// long as the number itself fits the declared width; the stored value has to
+// be an integer whatever its tag says
+fn fit_integer(type_str: &str, value: &Value) -> Result<Vec<u8>, CodecError> {
+    let value_type = match value {
+        Value::Single(inner, _) => inner.eth_type(),
+        Value::Collection(_) => value.eth_type(),
+    };
+    let is_signed = type_str.starts_with("int");
+    let same_kind = (type_str.starts_with("uint") && value_type.starts_with("uint"))
+        || (is_signed && value_type.starts_with("int"));
+    let width = get_bytes_from_type(type_str);
+    if value.is_collection() || !same_kind || width == 0 {
         return Err(CodecError::InvalidTypeAndValue(
             type_str.to_string(),
             value.to_string(),
         ));
     }
 
-    Ok(value.to_bytes_vec())
+    let bytes = value.to_bytes_vec();
+    let fill = if is_signed && bytes[0] & 0x80 != 0 {
+        0xff
+    } else {
+        0x00
+    };
+    if bytes.len() <= width {
+        let mut widened = vec![fill; width - bytes.len()];
+        widened.extend(bytes);
+        return Ok(widened);
+    }
+
+    let (high, low) = bytes.split_at(bytes.len() - width);
+    let low_fill = if is_signed && low[0] & 0x80 != 0 {
+        0xff
+    } else {
+        0x00
+    };
+    if high.iter().any(|b| *b != low_fill) {
+        return Err(CodecError::InvalidTypeAndValue(
+            type_str.to_string(),
+            format!("{} does not fit in {}", value.to_string(), type_str),
+        ));
+    }
+
+    Ok(low.to_vec())
 }
 
-fn encode_packed_array(type_str: &str, values: &Vec<Value>) -> Result<Vec<u8>, CodecError> {
+// Like `abi.encodePacked`, array elements keep their full 32-byte words;
+// solc has no packed form for dynamic, nested or tuple elements.
+fn encode_packed_array(arr_type_str: &str, values: &[Value]) -> Result<Vec<u8>, CodecError> {
+    let type_str = get_array_element_type(arr_type_str);
+    if is_dynamic(type_str) || is_array(type_str)?.0 || is_tuple(type_str)?.0 {
+        return Err(CodecError::InvalidArray(arr_type_str.to_string()));
+    }
+
     let mut encoded = Vec::new();
     for value in values {
-        let encoded_value = encode_packed(type_str, value)?;
-        encoded = encoded_value
-            .into_iter()
-            .chain(encoded.into_iter())
-            .collect();
+        encoded.extend(encode(type_str, value, false)?);
     }
 
     Ok(encoded)
@@ -242,7 +555,343 @@ mod encode_tests {
     use crate::build_values;
     use crate::codec::traits::BoxTrait;
     use crate::codec::types::ValueBuilder;
-    use alloy_primitives::{Address, aliases::*, hex};
+    use crate::codec::utils::to_single;
+    use alloy_primitives::{Address, Bytes, FixedBytes, aliases::*};
+
+    #[test]
+    fn test_abi_encode_selector_with_returns() {
+        assert_eq!(
+            abi_encode_selector("balanceOf(address) external view returns (uint256)").unwrap(),
+            hex!("70a08231")
+        );
+        assert_eq!(
+            abi_encode_selector("balanceOf(address)").unwrap(),
+            hex!("70a08231")
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_packed_widths() {
+        let timestamp = 1_700_000_000u64;
+        let values = ValueBuilder::new()
+            .add(U256::from(timestamp))
+            .add(I256::try_from(-2).unwrap())
+            .add(Address::repeat_byte(0x44))
+            .build();
+        let entries: Vec<(Value, usize)> = values.into_iter().zip([6, 2, 20]).collect();
+
+        let encoded = abi_encode_packed_widths(&entries).unwrap();
+        assert_eq!(&encoded[..6], &timestamp.to_be_bytes()[2..]);
+        assert_eq!(&encoded[6..8], &[0xff, 0xfe]);
+        assert_eq!(&encoded[8..], &[0x44; 20]);
+
+        let overflowing = ValueBuilder::new().add(U256::from(1u64 << 48)).build();
+        assert!(abi_encode_packed_widths(&[(overflowing[0].clone(), 6)]).is_err());
+
+        let address = ValueBuilder::new().add(Address::ZERO).build();
+        assert!(abi_encode_packed_widths(&[(address[0].clone(), 6)]).is_err());
+    }
+
+    #[test]
+    fn test_abi_encode_selector_array() {
+        for signature in [
+            "transfer(address,uint256)",
+            "function balanceOf(address owner) external view returns (uint256)",
+        ] {
+            assert_eq!(
+                abi_encode_selector_array(signature).to_vec(),
+                abi_encode_selector(signature).unwrap()
+            );
+            assert_eq!(
+                try_abi_encode_selector_array(signature).unwrap(),
+                abi_encode_selector_array(signature)
+            );
+        }
+        assert!(try_abi_encode_selector_array("transfer").is_err());
+        assert_eq!(
+            abi_encode_selector_array("transfer"),
+            selector_from_preimage(b"transfer")
+        );
+    }
+
+    #[test]
+    fn test_prepend_selector() {
+        let values = ValueBuilder::new()
+            .add(Address::ZERO)
+            .add(U256::from(1))
+            .build();
+        let args = abi_encode(&["address", "uint256"], &values).unwrap();
+
+        let calldata = prepend_selector("transfer(address,uint256)", &args).unwrap();
+        assert_eq!(calldata.len(), 4 + args.len());
+        assert_eq!(calldata[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(calldata[4..], args);
+        assert_eq!(
+            calldata,
+            abi_encode_with_selector(&[0xa9, 0x05, 0x9c, 0xbb], &["address", "uint256"], &values)
+                .unwrap()
+        );
+        assert!(prepend_selector("transfer", &args).is_err());
+    }
+
+    #[test]
+    fn test_keyword_function_name_selector() {
+        for signature in ["tuple(uint256)", "array(uint256)", "function(uint256)"] {
+            assert_eq!(
+                abi_encode_selector(&format!("function {} external", signature)).unwrap(),
+                keccak256(signature)[..4]
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_interface_id() {
+        let erc721 = [
+            "balanceOf(address)",
+            "ownerOf(uint256)",
+            "safeTransferFrom(address,address,uint256,bytes)",
+            "safeTransferFrom(address,address,uint256)",
+            "transferFrom(address,address,uint256)",
+            "approve(address,uint256)",
+            "setApprovalForAll(address,bool)",
+            "getApproved(uint256)",
+            "isApprovedForAll(address,address)",
+        ];
+        assert_eq!(compute_interface_id(&erc721).unwrap(), hex!("80ac58cd"));
+
+        // ERC-165 itself has a single function
+        assert_eq!(
+            compute_interface_id(&["supportsInterface(bytes4)"]).unwrap(),
+            hex!("01ffc9a7")
+        );
+        assert_eq!(compute_interface_id(&[]).unwrap(), [0u8; 4]);
+    }
+
+    #[test]
+    fn test_abi_encode_infer() {
+        let values = ValueBuilder::new()
+            .add(Address::repeat_byte(0x55))
+            .add(U256::from(1000))
+            .build();
+        assert_eq!(
+            abi_encode_infer("transfer", &values).unwrap(),
+            abi_encode_with_singature("transfer(address,uint256)", &values).unwrap()
+        );
+
+        let values = ValueBuilder::new()
+            .add_array(vec![U256::from(1), U256::from(2)])
+            .add_value(Value::new(
+                ValueBuilder::new().add(true).add(String::from("x")).build(),
+            ))
+            .build();
+        let encoded =
+            abi_encode_infer_with_types("f", &values, &["uint256[]", "(bool,string)"]).unwrap();
+        assert_eq!(
+            encoded[..4],
+            abi_encode_selector("f(uint256[],(bool,string))").unwrap()
+        );
+        assert_eq!(
+            abi_encode_infer_with_types("f", &values, &["uint256[]"]),
+            Err(CodecError::LengthsMismatch(2, 1))
+        );
+        assert_eq!(
+            abi_encode_infer("f", &values),
+            Err(CodecError::LengthsMismatch(2, 0))
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_infer_collection_shapes() {
+        // a one-element array stays an array
+        let values = ValueBuilder::new().add_array(vec![U256::from(1)]).build();
+        let encoded = abi_encode_infer_with_types("f", &values, &["uint256[]"]).unwrap();
+        assert_eq!(encoded[..4], hex!("7bc5bbbf"));
+        assert_eq!(
+            encoded,
+            abi_encode_with_singature("f(uint256[])", &values).unwrap()
+        );
+
+        // and a same-typed tuple stays a tuple
+        let values = ValueBuilder::new()
+            .add_value(Value::new(
+                ValueBuilder::new()
+                    .add(Address::repeat_byte(0x01))
+                    .add(Address::repeat_byte(0x02))
+                    .build(),
+            ))
+            .build();
+        let encoded = abi_encode_infer_with_types("f", &values, &["(address,address)"]).unwrap();
+        assert_eq!(encoded[..4], hex!("c33cda3d"));
+        assert_eq!(
+            encoded,
+            abi_encode_with_singature("f((address,address))", &values).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_calldata_gas_stats() {
+        // transfer(0x11..11, 1): selector, 12 zero + 20 address bytes, 31 zero + 0x01
+        let calldata = abi_encode_with_singature(
+            "transfer(address,uint256)",
+            &ValueBuilder::new()
+                .add(Address::repeat_byte(0x11))
+                .add(U256::from(1))
+                .build(),
+        )
+        .unwrap();
+
+        assert_eq!(calldata_gas_stats(&calldata), (43, 25, 43 * 4 + 25 * 16));
+        assert_eq!(calldata_gas_stats(&[]), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_selector_from_preimage() {
+        let signature = "transfer(address,uint256)";
+        assert_eq!(
+            selector_from_preimage(signature.as_bytes()).to_vec(),
+            abi_encode_selector(signature).unwrap()
+        );
+        assert_eq!(
+            selector_from_preimage(signature.as_bytes()),
+            hex!("a9059cbb")
+        );
+
+        // preimages need not be valid UTF-8
+        assert_eq!(
+            selector_from_preimage(&[0xff, 0xfe]),
+            keccak256([0xff, 0xfe])[..4]
+        );
+    }
+
+    #[test]
+    fn test_error_selector_and_event_topic() {
+        let selector = error_selector("InsufficientBalance(uint256,uint256)").unwrap();
+        assert_eq!(selector, hex!("cf479181"));
+        assert_eq!(
+            abi_encode_selector("InsufficientBalance(uint256,uint256)").unwrap(),
+            selector
+        );
+
+        assert_eq!(
+            event_topic("Transfer(address,address,uint256)"),
+            hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_narrowed_integers() {
+        let one = ValueBuilder::new().add(U256::from(1)).build();
+        assert_eq!(
+            abi_encode(&["uint128"], &one).unwrap(),
+            abi_encode(&["uint256"], &one).unwrap()
+        );
+        assert_eq!(abi_encode_packed(&["uint128"], &one).unwrap().len(), 16);
+
+        let too_wide = ValueBuilder::new().add(U256::from(1) << 128).build();
+        assert_eq!(
+            abi_encode(&["uint128"], &too_wide).unwrap_err(),
+            CodecError::InvalidTypeAndValue(
+                "uint128".to_string(),
+                "340282366920938463463374607431768211456 does not fit in uint128".to_string()
+            )
+        );
+
+        let minus_one = ValueBuilder::new().add(I256::MINUS_ONE).build();
+        assert_eq!(
+            abi_encode(&["int8"], &minus_one).unwrap(),
+            abi_encode(&["int256"], &minus_one).unwrap()
+        );
+        assert_eq!(
+            abi_encode_packed(&["int8"], &minus_one).unwrap(),
+            vec![0xff]
+        );
+
+        let small = ValueBuilder::new().add(I8::MINUS_ONE).build();
+        assert_eq!(abi_encode(&["int8"], &small).unwrap(), vec![0xff; 32]);
+
+        let mismatched = ValueBuilder::new().add(I256::MINUS_ONE).build();
+        assert!(abi_encode(&["uint8"], &mismatched).is_err());
+    }
+
+    #[test]
+    fn test_abi_encode_struct() {
+        let fields = [
+            ("owner", to_single(&Address::repeat_byte(0x42))),
+            ("name", to_single(&String::from("vault"))),
+        ];
+        let tuple = ValueBuilder::new()
+            .add_value(Value::new(
+                ValueBuilder::new()
+                    .add(Address::repeat_byte(0x42))
+                    .add(String::from("vault"))
+                    .build(),
+            ))
+            .build();
+
+        assert_eq!(
+            abi_encode_struct(&fields).unwrap(),
+            abi_encode(&["(address,string)"], &tuple).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_struct_same_typed_fields() {
+        // two addresses are still a static tuple, not an address[]
+        let fields = [
+            ("owner", to_single(&Address::repeat_byte(0x01))),
+            ("spender", to_single(&Address::repeat_byte(0x02))),
+        ];
+        let encoded = abi_encode_struct(&fields).unwrap();
+        assert_eq!(encoded.len(), 64);
+        assert_eq!(encoded[12..32], [0x01; 20]);
+        assert_eq!(encoded[44..64], [0x02; 20]);
+
+        // collection fields are typed explicitly
+        let ids = Value::new(vec![to_single(&U256::from(7))]);
+        let fields = [
+            ("owner", to_single(&Address::repeat_byte(0x01))),
+            ("ids", ids.clone()),
+        ];
+        let tuple = Value::new(vec![to_single(&Address::repeat_byte(0x01)), ids]);
+        assert_eq!(
+            abi_encode_struct_with_types(&fields, &["uint256[]"]).unwrap(),
+            abi_encode(&["(address,uint256[])"], &[tuple]).unwrap()
+        );
+        assert_eq!(
+            abi_encode_struct(&fields),
+            Err(CodecError::LengthsMismatch(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_format_calldata() {
+        let values = ValueBuilder::new()
+            .add(Address::repeat_byte(0x11))
+            .add(String::from("hi"))
+            .build();
+        let calldata = abi_encode_with_singature("set(address,string)", &values).unwrap();
+
+        let dump = format_calldata(&calldata);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            format!("selector: 0x{}", hex::encode(&calldata[..4]))
+        );
+        assert_eq!(
+            lines[1],
+            "0x00: 0000000000000000000000001111111111111111111111111111111111111111"
+        );
+        assert_eq!(
+            lines[2],
+            "0x20: 0000000000000000000000000000000000000000000000000000000000000040"
+        );
+        assert!(lines[4].starts_with("0x60: 6869"));
+
+        let without_selector = format_calldata(&calldata[4..]);
+        assert_eq!(without_selector.lines().count(), 4);
+        assert!(without_selector.starts_with("0x00: "));
+    }
 
     #[test]
     fn test_abi_encode_regular() {
@@ -279,6 +928,32 @@ mod encode_tests {
         );
     }
 
+    #[test]
+    fn test_abi_encode_mixed_array() {
+        let mixed = Value::new(vec![
+            to_single(&U256::from(1)),
+            to_single(&true),
+            to_single(&U256::from(2)),
+        ]);
+        assert_eq!(
+            abi_encode(&["uint256[]"], &[mixed]),
+            Err(CodecError::InvalidTypeAndValue(
+                "uint256[]".to_string(),
+                "array elements differ in type: uint256, bool, uint256".to_string()
+            ))
+        );
+
+        // inner arrays may differ in length
+        let nested = Value::new(vec![
+            Value::new(vec![Value::new(vec![to_single(&U256::from(1))])]),
+            Value::new(vec![Value::new(vec![
+                to_single(&U256::from(2)),
+                to_single(&U256::from(3)),
+            ])]),
+        ]);
+        assert!(abi_encode(&["(uint256[])[]"], &[nested]).is_ok());
+    }
+
     #[test]
     fn test_abi_encode_tuple() {
         let type_strs = vec!["address", "(string[],uint256,uint8)", "uint256"];
@@ -341,4 +1016,199 @@ mod encode_tests {
             "000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
         );
     }
+
+    #[test]
+    fn test_verify_packed_commitment() {
+        let type_strs = vec!["string", "string"];
+        let values = ValueBuilder::new()
+            .add(String::from("hel"))
+            .add(String::from("lo"))
+            .build();
+        // keccak256("hello")
+        let expected_hash =
+            hex!("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8");
+
+        assert!(verify_packed_commitment(&type_strs, &values, expected_hash).unwrap());
+        assert!(!verify_packed_commitment(&type_strs, &values, [0u8; 32]).unwrap());
+    }
+
+    #[test]
+    fn test_abi_encode_static_tuple() {
+        let type_strs = vec!["(uint256,address)", "string"];
+        let values = ValueBuilder::new()
+            .add_tuple(vec![
+                Box::new(U256::from(1)) as Box<dyn BoxTrait>,
+                Box::new(Address::repeat_byte(0x22)) as Box<dyn BoxTrait>,
+            ])
+            .add(String::from("hello"))
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "000000000000000000000000000000000000000000000000000000000000000100000000000000000000000022222222222222222222222222222222222222220000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000568656c6c6f000000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_static_element_array() {
+        let type_strs = vec!["uint256[]"];
+        let values = ValueBuilder::new()
+            .add_array(vec![U256::from(1), U256::from(2), U256::from(3)])
+            .build();
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_fixed_bytes_alignment() {
+        let values = ValueBuilder::new()
+            .add(FixedBytes::<4>::from(hex!("deadbeef")))
+            .add(U32::from(0xdeadbeefu32))
+            .add(Address::repeat_byte(0x01))
+            .build();
+        let type_strs = ["bytes4", "uint32", "address"];
+
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            hex::encode(&encoded[..32]),
+            "deadbeef00000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            hex::encode(&encoded[32..64]),
+            "00000000000000000000000000000000000000000000000000000000deadbeef"
+        );
+        assert_eq!(&encoded[64..76], &[0u8; 12]);
+
+        let decoded = crate::decode::abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), values[0].to_string());
+        assert_eq!(abi_encode(&type_strs, &decoded).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_abi_encode_word_multiple_bytes() {
+        for (length, tail_length) in [(32, 32), (33, 64), (64, 64)] {
+            let values = ValueBuilder::new()
+                .add(Bytes::from(vec![0xcd; length]))
+                .build();
+            let encoded = abi_encode(&["bytes"], &values).unwrap();
+
+            assert_eq!(encoded.len(), 64 + tail_length);
+            assert_eq!(U256::from_be_slice(&encoded[32..64]), U256::from(length));
+            assert_eq!(&encoded[64..64 + length], vec![0xcd; length].as_slice());
+            assert!(encoded[64 + length..].iter().all(|b| *b == 0));
+        }
+    }
+
+    #[test]
+    fn test_abi_encode_head_tail() {
+        let type_strs = ["uint256", "bytes", "address"];
+        let values = ValueBuilder::new()
+            .add(U256::from(9))
+            .add(Bytes::from(vec![0xab; 33]))
+            .add(Address::repeat_byte(0x01))
+            .build();
+
+        let (head, tail) = abi_encode_head_tail(&type_strs, &values).unwrap();
+        assert_eq!(head.len(), 3 * 32);
+        assert_eq!(tail.len(), 3 * 32);
+        assert_eq!(U256::from_be_slice(&head[32..64]), U256::from(head.len()));
+        assert_eq!(
+            [head, tail].concat(),
+            abi_encode(&type_strs, &values).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_to_writer() {
+        let type_strs = vec!["address", "string[2]", "uint256"];
+        let values = ValueBuilder::new()
+            .add(Address::ZERO)
+            .add_array(vec![
+                String::from("Hello, world!"),
+                String::from("Hello, world!"),
+            ])
+            .add(U256::from(1))
+            .build();
+
+        let mut writer = Vec::new();
+        let written = abi_encode_to_writer(&mut writer, &type_strs, &values).unwrap();
+        let encoded = abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(written, encoded.len());
+        assert_eq!(writer, encoded);
+    }
+
+    #[test]
+    fn test_abi_encode_normalized() {
+        let type_strs = vec!["UINT256", "Address"];
+        let values = ValueBuilder::new()
+            .add(U256::from(1))
+            .add(Address::ZERO)
+            .build();
+
+        assert_eq!(
+            abi_encode(&type_strs, &values),
+            Err(CodecError::InvalidTypeAndValue(
+                "UINT256".to_string(),
+                "1".to_string()
+            ))
+        );
+        assert_eq!(
+            abi_encode_normalized(&type_strs, &values).unwrap(),
+            abi_encode(&["uint256", "address"], &values).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_packed_strings() {
+        let values = ValueBuilder::new()
+            .add(String::from("ab"))
+            .add(String::from("cd"))
+            .build();
+        let encoded = abi_encode_packed(&["string", "string"], &values).unwrap();
+        assert_eq!(encoded, b"abcd");
+
+        let values = ValueBuilder::new().add(Bytes::from(hex!("1234"))).build();
+        let encoded = abi_encode_packed(&["bytes"], &values).unwrap();
+        assert_eq!(hex::encode(&encoded), "1234");
+    }
+
+    #[test]
+    fn test_abi_encode_packed_array_order() {
+        let values = ValueBuilder::new()
+            .add_array(vec![U8::from(1), U8::from(2)])
+            .add(Bytes::from(hex!("1234")))
+            .build();
+        let encoded = abi_encode_packed(&["uint8[]", "bytes"], &values).unwrap();
+        assert_eq!(hex::encode(&encoded), format!("{:064x}{:064x}1234", 1, 2));
+    }
+
+    #[test]
+    fn test_abi_encode_packed_array_words() {
+        // every element takes a full word, as in abi.encodePacked
+        let values = ValueBuilder::new()
+            .add_array(vec![Address::repeat_byte(0x01)])
+            .add_array(vec![FixedBytes::<2>::from(hex!("abcd"))])
+            .add_array(vec![I8::try_from(-1).unwrap()])
+            .build();
+        let encoded = abi_encode_packed(&["address[]", "bytes2[]", "int8[]"], &values).unwrap();
+        assert_eq!(encoded.len(), 96);
+        assert_eq!(encoded[..12], [0u8; 12]);
+        assert_eq!(encoded[12..32], [0x01; 20]);
+        assert_eq!(encoded[32..34], hex!("abcd"));
+        assert_eq!(encoded[34..64], [0u8; 30]);
+        assert_eq!(encoded[64..], [0xff; 32]);
+
+        let values = ValueBuilder::new()
+            .add_array(vec![String::from("ab"), String::from("cd")])
+            .build();
+        assert_eq!(
+            abi_encode_packed(&["string[]"], &values),
+            Err(CodecError::InvalidArray("string[]".to_string()))
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use alloy_primitives::aliases::U256;
+
 #[derive(Debug, PartialEq, Eq, thiserror::Error)]
 #[non_exhaustive]
 pub enum CodecError {
@@ -8,18 +10,34 @@ pub enum CodecError {
     InvalidTuple(String),
     #[error("Invalid function signature: {0}")]
     InvalidFunctionSignature(String),
+    #[error("Recursion limit exceeded: depth {0}")]
+    RecursionLimitExceeded(usize),
 
     // encode
     #[error("Invalid type and value: {0}")]
     InvalidTypeAndValue(String, String),
     #[error("Lengths mismatch: {0} != {1}")]
     LengthsMismatch(usize, usize),
+    #[error("Write error: {0}")]
+    WriteError(String),
 
     // decode
     #[error("Invalid value length: {0}")]
     InvalidValueLength(usize),
+    #[error("Length or offset does not fit in usize: {0}")]
+    LengthOverflow(U256),
     #[error("Unsupported type: {0}")]
     UnsupportedType(String),
     #[error("Invalid selector")]
     InvalidSelector,
+    #[error("Malformed offsets: {0}")]
+    MalformedOffsets(String),
+    #[error("Out of bounds: {0} > {1}")]
+    OutOfBounds(usize, usize),
+    #[error("Dirty padding bytes")]
+    DirtyPaddingBytes,
+    #[error("Work limit exceeded: {0} words")]
+    WorkLimitExceeded(usize),
+    #[error("Topic count mismatch: expected {expected}, got {got}")]
+    TopicCountMismatch { expected: usize, got: usize },
 }
@@ -0,0 +1,160 @@
+use crate::common::{get_parameter_types, is_tuple, split_parameter_name, validate_type_syntax};
+use crate::errors::CodecError;
+use serde_json::{Value as JsonValue, json};
+
+// Canonical `name(type,...)` signature of a JSON ABI function, error or
+// event entry.
+pub fn signature_from_json(entry: &JsonValue) -> Result<String, CodecError> {
+    let name = entry["name"].as_str().ok_or_else(|| invalid_entry(entry))?;
+    let inputs = match &entry["inputs"] {
+        JsonValue::Null => Vec::new(),
+        inputs => json_param_types(inputs)?,
+    };
+
+    Ok(format!("{}({})", name, inputs.join(",")))
+}
+
+// Canonical type of a JSON ABI parameter. Only `type` is read: `tuple`,
+// `tuple[]`, `tuple[2][]` and so on are spelled out from `components`, at any
+// depth, while `internalType` (`struct Foo`) is ignored.
+pub fn json_param_type(param: &JsonValue) -> Result<String, CodecError> {
+    let type_str = param["type"].as_str().ok_or_else(|| invalid_entry(param))?;
+
+    match type_str.strip_prefix("tuple") {
+        Some(suffix) => Ok(format!(
+            "({}){}",
+            json_param_types(&param["components"])?.join(","),
+            suffix
+        )),
+        None => Ok(type_str.to_string()),
+    }
+}
+
+// The solc-style `inputs` array of a signature, the inverse of
+// `signature_from_json`. Names are kept where the signature has them and
+// left empty otherwise; tuples become `tuple` entries with `components`.
+pub fn signature_to_json_inputs(signature: &str) -> Result<JsonValue, CodecError> {
+    get_parameter_types(signature)?
+        .into_iter()
+        .map(param_to_json)
+        .collect::<Result<Vec<_>, _>>()
+        .map(JsonValue::Array)
+}
+
+fn param_to_json(param: &str) -> Result<JsonValue, CodecError> {
+    let (type_str, name) = split_parameter_name(param);
+    validate_type_syntax(type_str)?;
+    let name = name.unwrap_or_default();
+
+    let (is_tuple_type, members) = is_tuple(type_str)?;
+    if !is_tuple_type {
+        return Ok(json!({ "name": name, "type": type_str }));
+    }
+
+    // whatever follows the closing paren is the array suffix, e.g. `[2][]`
+    let suffix = &type_str[type_str.rfind(')').unwrap() + 1..];
+    let components = members
+        .into_iter()
+        .map(param_to_json)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(json!({
+        "name": name,
+        "type": format!("tuple{}", suffix),
+        "components": components,
+    }))
+}
+
+fn json_param_types(params: &JsonValue) -> Result<Vec<String>, CodecError> {
+    params
+        .as_array()
+        .ok_or_else(|| invalid_entry(params))?
+        .iter()
+        .map(json_param_type)
+        .collect()
+}
+
+fn invalid_entry(entry: &JsonValue) -> CodecError {
+    CodecError::InvalidFunctionSignature(entry.to_string())
+}
+
+#[cfg(test)]
+mod json_abi_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_components() {
+        let entry = json!({
+            "type": "function",
+            "name": "submit",
+            "inputs": [
+                {
+                    "name": "orders",
+                    "type": "tuple[]",
+                    "internalType": "struct Order[]",
+                    "components": [
+                        {
+                            "name": "leg",
+                            "type": "tuple",
+                            "internalType": "struct Leg",
+                            "components": [
+                                { "name": "amount", "type": "uint256", "internalType": "uint256" },
+                                { "name": "token", "type": "address", "internalType": "contract IERC20" }
+                            ]
+                        },
+                        { "name": "data", "type": "bytes", "internalType": "bytes" }
+                    ]
+                },
+                { "name": "deadline", "type": "uint64", "internalType": "uint64" }
+            ],
+            "outputs": []
+        });
+
+        assert_eq!(
+            json_param_type(&entry["inputs"][0]).unwrap(),
+            "((uint256,address),bytes)[]"
+        );
+        assert_eq!(
+            signature_from_json(&entry).unwrap(),
+            "submit(((uint256,address),bytes)[],uint64)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        assert!(json_param_type(&json!({ "type": "tuple" })).is_err());
+        assert!(json_param_type(&json!({ "name": "x" })).is_err());
+        assert_eq!(signature_from_json(&json!({ "name": "f" })).unwrap(), "f()");
+    }
+
+    #[test]
+    fn test_signature_to_json_inputs() {
+        let signature = "foo((uint256,address)[],bytes)";
+        let inputs = signature_to_json_inputs(signature).unwrap();
+        assert_eq!(
+            inputs,
+            json!([
+                {
+                    "name": "",
+                    "type": "tuple[]",
+                    "components": [
+                        { "name": "", "type": "uint256" },
+                        { "name": "", "type": "address" }
+                    ]
+                },
+                { "name": "", "type": "bytes" }
+            ])
+        );
+
+        let entry = json!({ "name": "foo", "inputs": inputs });
+        assert_eq!(signature_from_json(&entry).unwrap(), signature);
+
+        let inputs = signature_to_json_inputs("bar((bool flag,(bytes32)[2]) pair)").unwrap();
+        assert_eq!(inputs[0]["name"], "pair");
+        assert_eq!(inputs[0]["components"][0]["name"], "flag");
+        assert_eq!(inputs[0]["components"][1]["type"], "tuple[2]");
+        assert_eq!(signature_to_json_inputs("baz()").unwrap(), json!([]));
+        assert!(signature_to_json_inputs("foo(uint256[)").is_err());
+    }
+}
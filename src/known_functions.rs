@@ -0,0 +1,91 @@
+use crate::codec::types::Value;
+use crate::common::get_parameter_types;
+use crate::decode::abi_decode;
+use alloy_primitives::hex;
+
+// selectors of common ERC-20, ERC-721 and WETH calls
+pub const KNOWN_FUNCTIONS: &[([u8; 4], &str)] = &[
+    (hex!("a9059cbb"), "transfer(address,uint256)"),
+    (hex!("095ea7b3"), "approve(address,uint256)"),
+    (hex!("23b872dd"), "transferFrom(address,address,uint256)"),
+    (hex!("70a08231"), "balanceOf(address)"),
+    (hex!("dd62ed3e"), "allowance(address,address)"),
+    (hex!("18160ddd"), "totalSupply()"),
+    (hex!("d0e30db0"), "deposit()"),
+    (hex!("2e1a7d4d"), "withdraw(uint256)"),
+    (hex!("40c10f19"), "mint(address,uint256)"),
+    (hex!("42966c68"), "burn(uint256)"),
+    (
+        hex!("42842e0e"),
+        "safeTransferFrom(address,address,uint256)",
+    ),
+    (hex!("a22cb465"), "setApprovalForAll(address,bool)"),
+    (hex!("6352211e"), "ownerOf(uint256)"),
+];
+
+pub fn known_signature(selector: &[u8; 4]) -> Option<&'static str> {
+    KNOWN_FUNCTIONS
+        .iter()
+        .find(|(known, _)| known == selector)
+        .map(|(_, signature)| *signature)
+}
+
+// decodes calldata of a well-known call without an ABI, returning the
+// signature it matched; unknown selectors and malformed arguments give None
+pub fn decode_known(calldata: &[u8]) -> Option<(String, Vec<Value>)> {
+    let selector: &[u8; 4] = calldata.get(..4)?.try_into().ok()?;
+    let signature = known_signature(selector)?;
+    let type_strs = get_parameter_types(signature).ok()?;
+    let values = abi_decode(&type_strs, &calldata[4..]).ok()?;
+
+    Some((signature.to_string(), values))
+}
+
+#[cfg(test)]
+mod known_functions_tests {
+    use super::*;
+    use crate::codec::traits::FromValue;
+    use crate::codec::types::ValueBuilder;
+    use crate::encode::{abi_encode_selector_array, abi_encode_with_singature};
+    use alloy_primitives::Address;
+    use alloy_primitives::aliases::U256;
+
+    #[test]
+    fn test_known_selectors() {
+        for (selector, signature) in KNOWN_FUNCTIONS {
+            assert_eq!(abi_encode_selector_array(signature), *selector);
+        }
+    }
+
+    #[test]
+    fn test_decode_known_transfer() {
+        let to = Address::repeat_byte(0x11);
+        let values = ValueBuilder::new().add(to).add(U256::from(1000)).build();
+        let calldata = abi_encode_with_singature("transfer(address,uint256)", &values).unwrap();
+
+        let (signature, values) = decode_known(&calldata).unwrap();
+        assert_eq!(signature, "transfer(address,uint256)");
+        assert_eq!(Address::from_value(&values[0]).unwrap(), to);
+        assert_eq!(U256::from_value(&values[1]).unwrap(), U256::from(1000));
+    }
+
+    #[test]
+    fn test_decode_known_approve() {
+        let calldata = hex!(
+            "095ea7b3000000000000000000000000000000000022d473030f116ddee9f6b43ac78ba3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+
+        let (signature, values) = decode_known(&calldata).unwrap();
+        assert_eq!(signature, "approve(address,uint256)");
+        assert_eq!(
+            Address::from_value(&values[0]).unwrap(),
+            Address::from(hex!("000000000022d473030f116ddee9f6b43ac78ba3"))
+        );
+        assert_eq!(U256::from_value(&values[1]).unwrap(), U256::MAX);
+
+        assert_eq!(decode_known(&hex!("d0e30db0")).unwrap().1.len(), 0);
+        assert!(decode_known(&calldata[..40]).is_none());
+        assert!(decode_known(&hex!("deadbeef")).is_none());
+        assert!(decode_known(&hex!("a905")).is_none());
+    }
+}
@@ -1,6 +1,19 @@
+pub mod abi_type;
 #[macro_use]
 pub mod codec;
 pub mod common;
+pub mod cursor;
 pub mod decode;
 pub mod encode;
 pub mod errors;
+#[cfg(feature = "json")]
+pub mod json_abi;
+pub mod known_functions;
+pub mod registry;
+pub mod revert;
+pub mod signature;
+pub mod standard_events;
+pub mod storage;
+
+#[cfg(feature = "derive")]
+pub use eth_abi_derive::{AbiDecode, AbiEncode};
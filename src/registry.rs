@@ -0,0 +1,317 @@
+use crate::codec::traits::{EncodeCodec, FromValue};
+use crate::codec::types::{Value, create_value};
+use crate::common::{get_array_element_type, is_array, is_tuple};
+use crate::decode::abi_decode;
+use crate::encode::abi_encode;
+use crate::errors::CodecError;
+use alloy_primitives::FixedBytes;
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+type EncodeFn = dyn Fn(&Value) -> Result<[u8; 32], CodecError> + Send + Sync;
+type DecodeFn = dyn Fn(&[u8; 32]) -> Result<Value, CodecError> + Send + Sync;
+type FormatFn = dyn Fn(&Value) -> String + Send + Sync;
+
+struct CustomType {
+    encode: Box<EncodeFn>,
+    decode: Box<DecodeFn>,
+    format: Arc<FormatFn>,
+}
+
+// Application-specific single-word types. Each custom name is encoded and
+// decoded through its registered closures, anywhere it appears in a type
+// string, before the built-in types are handled. Only the registry's own
+// `abi_encode`/`abi_decode` and a `Codec` given it via `with_registry` see
+// custom names; the free functions don't know about them.
+#[derive(Default)]
+pub struct TypeRegistry {
+    types: HashMap<String, CustomType>,
+}
+
+// A decoded custom value: the value produced by the decode closure, shown
+// through the registered formatter.
+#[derive(Clone)]
+pub struct CustomValue {
+    inner: Value,
+    format: Arc<FormatFn>,
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<E, D, F>(&mut self, name: &str, encode: E, decode: D, format: F) -> &mut Self
+    where
+        E: Fn(&Value) -> Result<[u8; 32], CodecError> + Send + Sync + 'static,
+        D: Fn(&[u8; 32]) -> Result<Value, CodecError> + Send + Sync + 'static,
+        F: Fn(&Value) -> String + Send + Sync + 'static,
+    {
+        self.types.insert(
+            name.to_string(),
+            CustomType {
+                encode: Box::new(encode),
+                decode: Box::new(decode),
+                format: Arc::new(format),
+            },
+        );
+        self
+    }
+
+    pub fn abi_encode(&self, type_strs: &[&str], values: &[Value]) -> Result<Vec<u8>, CodecError> {
+        let (builtin_types, builtin_values) = self.lower(type_strs, values)?;
+        let builtin_types: Vec<&str> = builtin_types.iter().map(|t| t.as_str()).collect();
+
+        abi_encode(&builtin_types, &builtin_values)
+    }
+
+    pub fn abi_decode(
+        &self,
+        type_strs: &[&str],
+        encoded_values: &[u8],
+    ) -> Result<Vec<Value>, CodecError> {
+        let builtin_types = self.resolve_all(type_strs);
+        let builtin_types: Vec<&str> = builtin_types.iter().map(|t| t.as_str()).collect();
+
+        self.restore(type_strs, abi_decode(&builtin_types, encoded_values)?)
+    }
+
+    // the built-in types and values to encode in place of custom ones
+    pub(crate) fn lower(
+        &self,
+        type_strs: &[&str],
+        values: &[Value],
+    ) -> Result<(Vec<String>, Vec<Value>), CodecError> {
+        if type_strs.len() != values.len() {
+            return Err(CodecError::LengthsMismatch(type_strs.len(), values.len()));
+        }
+
+        let builtin_values = type_strs
+            .iter()
+            .zip(values)
+            .map(|(t, v)| self.lower_custom(t, v))
+            .collect::<Result<Vec<Value>, CodecError>>()?;
+
+        Ok((self.resolve_all(type_strs), builtin_values))
+    }
+
+    // decoded built-in values turned back into the custom ones
+    pub(crate) fn restore(
+        &self,
+        type_strs: &[&str],
+        values: Vec<Value>,
+    ) -> Result<Vec<Value>, CodecError> {
+        values
+            .into_iter()
+            .zip(type_strs)
+            .map(|(v, t)| self.restore_custom(t, v))
+            .collect()
+    }
+
+    pub(crate) fn resolve_all(&self, type_strs: &[&str]) -> Vec<String> {
+        type_strs.iter().map(|t| self.resolve(t)).collect()
+    }
+
+    // custom names are carried as raw bytes32 words by the built-in codec
+    fn resolve(&self, type_str: &str) -> String {
+        let mut resolved = String::with_capacity(type_str.len());
+        let mut rest = type_str;
+        while !rest.is_empty() {
+            let token_end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len())
+                .max(1);
+            let (token, tail) = rest.split_at(token_end);
+            if self.types.contains_key(token) {
+                resolved.push_str("bytes32");
+            } else {
+                resolved.push_str(token);
+            }
+            rest = tail;
+        }
+
+        resolved
+    }
+
+    fn lower_custom(&self, type_str: &str, value: &Value) -> Result<Value, CodecError> {
+        if let Some(custom) = self.types.get(type_str) {
+            let word = (custom.encode)(unwrap_custom(value))?;
+            return Ok(create_value(FixedBytes::<32>::from(word), "bytes32"));
+        }
+
+        self.map_members(type_str, value, |t, v| self.lower_custom(t, v))
+    }
+
+    fn restore_custom(&self, type_str: &str, value: Value) -> Result<Value, CodecError> {
+        if let Some(custom) = self.types.get(type_str) {
+            let word = FixedBytes::<32>::from_value(&value)?;
+            let custom_value = CustomValue {
+                inner: (custom.decode)(&word.0)?,
+                format: custom.format.clone(),
+            };
+            return Ok(create_value(custom_value, type_str));
+        }
+
+        self.map_members(type_str, &value, |t, v| self.restore_custom(t, v.clone()))
+    }
+
+    // applies `f` to the members of arrays and tuples, leaving scalars as-is
+    fn map_members<F>(&self, type_str: &str, value: &Value, f: F) -> Result<Value, CodecError>
+    where
+        F: Fn(&str, &Value) -> Result<Value, CodecError>,
+    {
+        let members: Vec<&str> = if is_array(type_str)?.0 {
+            let Value::Collection(values) = value else {
+                return Err(CodecError::InvalidArray(type_str.to_string()));
+            };
+            vec![get_array_element_type(type_str); values.len()]
+        } else if let (true, tuple_types) = is_tuple(type_str)? {
+            tuple_types
+        } else {
+            return Ok(value.clone());
+        };
+
+        match value {
+            Value::Collection(values) if values.len() == members.len() => Ok(Value::Collection(
+                members
+                    .iter()
+                    .zip(values)
+                    .map(|(t, v)| f(t, v))
+                    .collect::<Result<Vec<Value>, CodecError>>()?,
+            )),
+            Value::Collection(values) => {
+                Err(CodecError::LengthsMismatch(members.len(), values.len()))
+            }
+            Value::Single(_, _) => Err(CodecError::InvalidTuple(type_str.to_string())),
+        }
+    }
+}
+
+fn unwrap_custom(value: &Value) -> &Value {
+    if let Value::Single(inner, _) = value {
+        let any: &dyn Any = inner.as_ref();
+        if let Some(custom) = any.downcast_ref::<CustomValue>() {
+            return &custom.inner;
+        }
+    }
+
+    value
+}
+
+impl fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.types.keys()).finish()
+    }
+}
+
+impl fmt::Debug for CustomValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomValue").field(&self.inner).finish()
+    }
+}
+
+impl EncodeCodec for CustomValue {
+    fn to_bytes_vec(&self) -> Vec<u8> {
+        self.inner.to_bytes_vec()
+    }
+
+    fn bytes_length(&self) -> usize {
+        self.inner.bytes_length()
+    }
+
+    fn eth_type(&self) -> String {
+        self.inner.eth_type()
+    }
+
+    fn to_string(&self) -> String {
+        (self.format)(&self.inner)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        match &self.inner {
+            Value::Single(inner, _) => inner.as_any(),
+            Value::Collection(_) => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+    use crate::codec::config::Codec;
+    use crate::codec::types::ValueBuilder;
+    use alloy_primitives::aliases::U16;
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        registry.register(
+            "percent",
+            |value| {
+                let encoded = abi_encode(&["uint16"], std::slice::from_ref(value))?;
+                Ok(encoded.try_into().unwrap())
+            },
+            |word| Ok(abi_decode(&["uint16"], word)?.remove(0)),
+            |value| format!("{}%", value.to_string()),
+        );
+        registry
+    }
+
+    #[test]
+    fn test_custom_type() {
+        let registry = registry();
+        let values = ValueBuilder::new().add(U16::from(25)).build();
+
+        let encoded = registry.abi_encode(&["percent"], &values).unwrap();
+        assert_eq!(encoded, abi_encode(&["uint16"], &values).unwrap());
+
+        let decoded = registry.abi_decode(&["percent"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "25%");
+        assert_eq!(decoded[0].eth_type(), "percent");
+        assert_eq!(U16::from_value(&decoded[0]).unwrap(), U16::from(25));
+        assert_eq!(
+            registry.abi_encode(&["percent"], &decoded).unwrap(),
+            encoded
+        );
+    }
+
+    #[test]
+    fn test_nested_custom_type() {
+        let registry = registry();
+        let type_strs = ["(percent,string)[]"];
+        let values = ValueBuilder::new()
+            .add_value(Value::new(vec![Value::new(
+                ValueBuilder::new()
+                    .add(U16::from(5))
+                    .add(String::from("fee"))
+                    .build(),
+            )]))
+            .build();
+
+        let encoded = registry.abi_encode(&type_strs, &values).unwrap();
+        assert_eq!(
+            encoded,
+            abi_encode(&["(uint16,string)[]"], &values).unwrap()
+        );
+
+        let decoded = registry.abi_decode(&type_strs, &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "5%, fee");
+    }
+
+    #[test]
+    fn test_codec_with_registry() {
+        fn shareable<T: Send + Sync>(_: &T) {}
+        let registry = Arc::new(registry());
+        shareable(&registry);
+
+        let codec = Codec::strict().with_registry(registry);
+        let values = ValueBuilder::new().add(U16::from(25)).build();
+        let encoded = codec.encode(&["percent"], &values).unwrap();
+        assert_eq!(encoded, abi_encode(&["uint16"], &values).unwrap());
+
+        let decoded = codec.decode(&["percent"], &encoded).unwrap();
+        assert_eq!(decoded[0].to_string(), "25%");
+        assert!(Codec::strict().encode(&["percent"], &values).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use crate::codec::traits::FromValue;
+use crate::decode::abi_decode_with_signature;
+use crate::encode::abi_encode_selector_array;
+use crate::errors::CodecError;
+use alloy_primitives::aliases::U256;
+use std::fmt;
+
+const ERROR_SIGNATURE: &str = "Error(string)";
+const PANIC_SIGNATURE: &str = "Panic(uint256)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevertReason {
+    Error(String),
+    Panic(PanicCode, U256),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicCode {
+    Generic,
+    Assert,
+    ArithmeticOverflow,
+    DivisionByZero,
+    InvalidEnumValue,
+    InvalidStorageByteArray,
+    EmptyArrayPop,
+    ArrayOutOfBounds,
+    OutOfMemory,
+    UninitializedFunction,
+    Unknown,
+}
+
+impl PanicCode {
+    pub fn from_code(code: U256) -> Self {
+        if code > U256::from(u8::MAX) {
+            return PanicCode::Unknown;
+        }
+
+        match code.to::<u8>() {
+            0x00 => PanicCode::Generic,
+            0x01 => PanicCode::Assert,
+            0x11 => PanicCode::ArithmeticOverflow,
+            0x12 => PanicCode::DivisionByZero,
+            0x21 => PanicCode::InvalidEnumValue,
+            0x22 => PanicCode::InvalidStorageByteArray,
+            0x31 => PanicCode::EmptyArrayPop,
+            0x32 => PanicCode::ArrayOutOfBounds,
+            0x41 => PanicCode::OutOfMemory,
+            0x51 => PanicCode::UninitializedFunction,
+            _ => PanicCode::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for PanicCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            PanicCode::Generic => "generic compiler panic",
+            PanicCode::Assert => "assertion failed",
+            PanicCode::ArithmeticOverflow => "arithmetic overflow",
+            PanicCode::DivisionByZero => "division or modulo by zero",
+            PanicCode::InvalidEnumValue => "invalid enum value",
+            PanicCode::InvalidStorageByteArray => "invalid storage byte array",
+            PanicCode::EmptyArrayPop => "pop on empty array",
+            PanicCode::ArrayOutOfBounds => "array index out of bounds",
+            PanicCode::OutOfMemory => "out of memory",
+            PanicCode::UninitializedFunction => "uninitialized function",
+            PanicCode::Unknown => "unknown panic code",
+        };
+
+        write!(f, "{}", reason)
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Error(message) => write!(f, "{}", message),
+            RevertReason::Panic(PanicCode::Unknown, code) => {
+                write!(f, "{} 0x{:x}", PanicCode::Unknown, code)
+            }
+            RevertReason::Panic(panic_code, _) => write!(f, "{}", panic_code),
+        }
+    }
+}
+
+pub fn abi_decode_revert(data: &[u8]) -> Result<RevertReason, CodecError> {
+    if data.len() < 4 {
+        return Err(CodecError::InvalidSelector);
+    }
+
+    if data[..4] == abi_encode_selector_array(ERROR_SIGNATURE) {
+        let values = abi_decode_with_signature(ERROR_SIGNATURE, data)?;
+        let message = String::from_value(&values[0])?;
+        Ok(RevertReason::Error(message))
+    } else if data[..4] == abi_encode_selector_array(PANIC_SIGNATURE) {
+        let values = abi_decode_with_signature(PANIC_SIGNATURE, data)?;
+        let code = U256::from_value(&values[0])?;
+        Ok(RevertReason::Panic(PanicCode::from_code(code), code))
+    } else {
+        Err(CodecError::InvalidSelector)
+    }
+}
+
+#[cfg(test)]
+mod revert_tests {
+    use super::*;
+    use crate::codec::types::ValueBuilder;
+    use crate::encode::abi_encode_with_selector;
+    use alloy_primitives::hex;
+
+    fn panic_data(code: u64) -> Vec<u8> {
+        let values = ValueBuilder::new().add(U256::from(code)).build();
+        abi_encode_with_selector(&hex!("4e487b71"), &["uint256"], &values).unwrap()
+    }
+
+    #[test]
+    fn test_abi_decode_revert_panic() {
+        let reason = abi_decode_revert(&panic_data(0x11)).unwrap();
+        assert_eq!(
+            reason,
+            RevertReason::Panic(PanicCode::ArithmeticOverflow, U256::from(0x11))
+        );
+        assert_eq!(reason.to_string(), "arithmetic overflow");
+
+        let reason = abi_decode_revert(&panic_data(0x01)).unwrap();
+        assert_eq!(reason.to_string(), "assertion failed");
+
+        let reason = abi_decode_revert(&panic_data(0x32)).unwrap();
+        assert_eq!(reason.to_string(), "array index out of bounds");
+    }
+
+    #[test]
+    fn test_abi_decode_revert_unknown_panic() {
+        let reason = abi_decode_revert(&panic_data(0x99)).unwrap();
+        assert_eq!(
+            reason,
+            RevertReason::Panic(PanicCode::Unknown, U256::from(0x99))
+        );
+        assert_eq!(reason.to_string(), "unknown panic code 0x99");
+    }
+
+    #[test]
+    fn test_abi_decode_revert_error() {
+        let data = hex!(
+            "08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000d48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+        );
+        let reason = abi_decode_revert(&data).unwrap();
+        assert_eq!(reason, RevertReason::Error("Hello, world!".to_string()));
+    }
+
+    #[test]
+    fn test_abi_decode_revert_invalid_selector() {
+        assert_eq!(
+            abi_decode_revert(&hex!("deadbeef")),
+            Err(CodecError::InvalidSelector)
+        );
+    }
+}
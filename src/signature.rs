@@ -0,0 +1,109 @@
+use crate::common::validate_type_syntax;
+use crate::encode::selector_from_preimage;
+use crate::errors::CodecError;
+use std::cell::OnceCell;
+
+// Builds a canonical `name(type,...)` signature piece by piece. The selector
+// is computed on first use and kept until the signature changes.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureBuilder {
+    name: String,
+    params: Vec<String>,
+    selector: OnceCell<[u8; 4]>,
+}
+
+impl SignatureBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(&mut self, name: &str) -> &mut Self {
+        self.name = name.trim().to_string();
+        self.selector = OnceCell::new();
+        self
+    }
+
+    pub fn param(&mut self, type_str: &str) -> &mut Self {
+        let type_str: String = type_str.chars().filter(|c| !c.is_whitespace()).collect();
+        self.params.push(type_str);
+        self.selector = OnceCell::new();
+        self
+    }
+
+    pub fn tuple_param(&mut self, type_strs: &[&str]) -> &mut Self {
+        self.param(&format!("({})", type_strs.join(",")))
+    }
+
+    // a dynamic array parameter of the given element type
+    pub fn array_of(&mut self, type_str: &str) -> &mut Self {
+        self.param(&format!("{}[]", type_str))
+    }
+
+    pub fn signature(&self) -> Result<String, CodecError> {
+        if self.name.is_empty() {
+            return Err(CodecError::InvalidFunctionSignature(format!(
+                "missing name: ({})",
+                self.params.join(",")
+            )));
+        }
+        for param in &self.params {
+            validate_type_syntax(param)?;
+        }
+
+        Ok(format!("{}({})", self.name, self.params.join(",")))
+    }
+
+    pub fn selector(&self) -> Result<[u8; 4], CodecError> {
+        if let Some(selector) = self.selector.get() {
+            return Ok(*selector);
+        }
+
+        let selector = selector_from_preimage(self.signature()?.as_bytes());
+        Ok(*self.selector.get_or_init(|| selector))
+    }
+}
+
+#[cfg(test)]
+mod signature_tests {
+    use super::*;
+    use crate::encode::abi_encode_selector;
+
+    #[test]
+    fn test_signature_builder() {
+        let mut builder = SignatureBuilder::new();
+        builder.name("transfer").param("address").param("uint256");
+        assert_eq!(builder.signature().unwrap(), "transfer(address,uint256)");
+        assert_eq!(
+            builder.selector().unwrap().to_vec(),
+            abi_encode_selector("transfer(address,uint256)").unwrap()
+        );
+
+        // the cached selector follows later changes
+        builder.tuple_param(&["bool", "bytes"]).array_of("uint8");
+        assert_eq!(
+            builder.signature().unwrap(),
+            "transfer(address,uint256,(bool,bytes),uint8[])"
+        );
+        assert_eq!(
+            builder.selector().unwrap().to_vec(),
+            abi_encode_selector("transfer(address,uint256,(bool,bytes),uint8[])").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_signature_builder_errors() {
+        assert!(
+            SignatureBuilder::new()
+                .param("uint256")
+                .signature()
+                .is_err()
+        );
+        assert_eq!(
+            SignatureBuilder::new()
+                .name("f")
+                .param("uint256[)]")
+                .selector(),
+            Err(CodecError::InvalidTuple("uint256[)]".to_string()))
+        );
+    }
+}
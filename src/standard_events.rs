@@ -0,0 +1,127 @@
+use crate::codec::traits::FromValue;
+use crate::codec::types::Value;
+use crate::decode::abi_decode_log;
+use crate::errors::CodecError;
+use alloy_primitives::aliases::U256;
+use alloy_primitives::{Address, hex};
+
+pub const TRANSFER_SIGNATURE: &str = "Transfer(address,address,uint256)";
+pub const APPROVAL_SIGNATURE: &str = "Approval(address,address,uint256)";
+
+pub const TRANSFER_TOPIC: [u8; 32] =
+    hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+pub const APPROVAL_TOPIC: [u8; 32] =
+    hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+// ERC-20 keeps `value` in the log data, ERC-721 indexes `tokenId`
+const ERC20_TRANSFER_INDEXED: [bool; 3] = [true, true, false];
+const ERC721_TRANSFER_INDEXED: [bool; 3] = [true, true, true];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc721Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub token_id: U256,
+}
+
+pub fn decode_erc20_transfer(topics: &[Vec<u8>], data: &[u8]) -> Result<Erc20Transfer, CodecError> {
+    let values = abi_decode_log(TRANSFER_SIGNATURE, &ERC20_TRANSFER_INDEXED, topics, data)?;
+    let (from, to, value) = transfer_fields(&values)?;
+
+    Ok(Erc20Transfer { from, to, value })
+}
+
+pub fn decode_erc721_transfer(
+    topics: &[Vec<u8>],
+    data: &[u8],
+) -> Result<Erc721Transfer, CodecError> {
+    let values = abi_decode_log(TRANSFER_SIGNATURE, &ERC721_TRANSFER_INDEXED, topics, data)?;
+    let (from, to, token_id) = transfer_fields(&values)?;
+
+    Ok(Erc721Transfer { from, to, token_id })
+}
+
+fn transfer_fields(values: &[Value]) -> Result<(Address, Address, U256), CodecError> {
+    match values {
+        [from, to, amount] => Ok((
+            Address::from_value(from)?,
+            Address::from_value(to)?,
+            U256::from_value(amount)?,
+        )),
+        _ => Err(CodecError::LengthsMismatch(3, values.len())),
+    }
+}
+
+#[cfg(test)]
+mod standard_events_tests {
+    use super::*;
+    use crate::encode::event_topic;
+    use alloy_primitives::address;
+
+    #[test]
+    fn test_topic_constants() {
+        assert_eq!(TRANSFER_TOPIC, event_topic(TRANSFER_SIGNATURE));
+        assert_eq!(APPROVAL_TOPIC, event_topic(APPROVAL_SIGNATURE));
+    }
+
+    #[test]
+    fn test_decode_erc20_transfer() {
+        // a hand-built log for a 100 USDC (6 decimals) transfer
+        let topics = vec![
+            TRANSFER_TOPIC.to_vec(),
+            hex!("00000000000000000000000028c6c06298d514db089934071355e5743bf21d60").to_vec(),
+            hex!("000000000000000000000000a9d1e08c7793af67e9d92fe308d5697fb81d3e43").to_vec(),
+        ];
+        let data = hex!("0000000000000000000000000000000000000000000000000000000005f5e100");
+
+        let transfer = decode_erc20_transfer(&topics, &data).unwrap();
+        assert_eq!(
+            transfer,
+            Erc20Transfer {
+                from: address!("28c6c06298d514db089934071355e5743bf21d60"),
+                to: address!("a9d1e08c7793af67e9d92fe308d5697fb81d3e43"),
+                value: U256::from(100_000_000u64),
+            }
+        );
+
+        // an ERC-721 log carries one topic too many for the ERC-20 layout
+        let mut nft_topics = topics.clone();
+        nft_topics.push([0u8; 32].to_vec());
+        assert!(decode_erc20_transfer(&nft_topics, &[]).is_err());
+    }
+
+    #[test]
+    fn test_decode_erc721_transfer() {
+        // a hand-built mint log: token 8817 transferred from the zero address
+        let topics = vec![
+            TRANSFER_TOPIC.to_vec(),
+            [0u8; 32].to_vec(),
+            hex!("0000000000000000000000009a5fc0a8a8d1f8a1b2e6b6a2a2e5e8b7b1f0c3d2").to_vec(),
+            hex!("0000000000000000000000000000000000000000000000000000000000002271").to_vec(),
+        ];
+
+        let transfer = decode_erc721_transfer(&topics, &[]).unwrap();
+        assert_eq!(
+            transfer,
+            Erc721Transfer {
+                from: Address::ZERO,
+                to: address!("9a5fc0a8a8d1f8a1b2e6b6a2a2e5e8b7b1f0c3d2"),
+                token_id: U256::from(8817u64),
+            }
+        );
+
+        let mut wrong_event = topics.clone();
+        wrong_event[0] = APPROVAL_TOPIC.to_vec();
+        assert_eq!(
+            decode_erc721_transfer(&wrong_event, &[]),
+            Err(CodecError::InvalidSelector)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::common::{get_array_element_type, get_bytes_from_type, is_array, is_tuple};
+use crate::errors::CodecError;
+
+// Storage position of a field: `bytes` starting at byte `offset` (counted from
+// the low-order end) of slot `slot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlot {
+    pub slot: usize,
+    pub offset: usize,
+    pub bytes: usize,
+}
+
+// Computes the Solidity storage layout of a struct with the given field
+// types. Elementary fields are packed into a slot while they fit; dynamic
+// types, static arrays and nested structs always start a fresh slot, and the
+// field following them does too.
+pub fn compute_storage_layout(types: &[&str]) -> Result<Vec<StorageSlot>, CodecError> {
+    let mut layout = Vec::with_capacity(types.len());
+    let mut slot = 0;
+    let mut offset = 0;
+
+    for t in types {
+        let (bytes, full_slots) = storage_size(t)?;
+        if offset > 0 && (full_slots || offset + bytes > 32) {
+            slot += 1;
+            offset = 0;
+        }
+
+        layout.push(StorageSlot {
+            slot,
+            offset,
+            bytes,
+        });
+
+        if full_slots {
+            slot += bytes.div_ceil(32);
+            offset = 0;
+        } else {
+            offset += bytes;
+        }
+    }
+
+    Ok(layout)
+}
+
+// Returns the number of bytes a field occupies and whether it takes whole
+// slots rather than being packed. Only `bytes`, `string` and `T[]` keep a
+// single slot for their contents; static arrays and structs lay out their
+// members in place even when those are dynamic.
+fn storage_size(t: &str) -> Result<(usize, bool), CodecError> {
+    let (is_array_type, size) = is_array(t)?;
+    if t == "bytes" || t == "string" || (is_array_type && size == 0) {
+        return Ok((32, true));
+    }
+
+    if is_array_type {
+        let element = get_array_element_type(t);
+        return Ok((slots_of(&vec![element; size])? * 32, true));
+    }
+
+    let (is_tuple_type, members) = is_tuple(t)?;
+    if is_tuple_type {
+        return Ok((slots_of(&members)? * 32, true));
+    }
+
+    match get_bytes_from_type(t) {
+        0 => Err(CodecError::UnsupportedType(t.to_string())),
+        bytes => Ok((bytes, false)),
+    }
+}
+
+fn slots_of(types: &[&str]) -> Result<usize, CodecError> {
+    let layout = compute_storage_layout(types)?;
+    Ok(layout.last().map_or(0, |last| {
+        last.slot + (last.offset + last.bytes).div_ceil(32)
+    }))
+}
+
+#[cfg(test)]
+mod storage_tests {
+    use super::*;
+
+    #[test]
+    fn test_packed_slot() {
+        let layout = compute_storage_layout(&["uint128", "uint128"]).unwrap();
+        assert_eq!(
+            layout,
+            vec![
+                StorageSlot {
+                    slot: 0,
+                    offset: 0,
+                    bytes: 16
+                },
+                StorageSlot {
+                    slot: 0,
+                    offset: 16,
+                    bytes: 16
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unpacked_slots() {
+        let layout = compute_storage_layout(&["uint128", "uint256"]).unwrap();
+        assert_eq!(
+            layout,
+            vec![
+                StorageSlot {
+                    slot: 0,
+                    offset: 0,
+                    bytes: 16
+                },
+                StorageSlot {
+                    slot: 1,
+                    offset: 0,
+                    bytes: 32
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mixed_layout() {
+        let layout =
+            compute_storage_layout(&["bool", "address", "string", "uint8", "uint64[3]", "bool"])
+                .unwrap();
+        let slots: Vec<(usize, usize)> = layout.iter().map(|s| (s.slot, s.offset)).collect();
+        assert_eq!(slots, vec![(0, 0), (0, 1), (1, 0), (2, 0), (3, 0), (4, 0)]);
+        assert_eq!(layout[4].bytes, 32);
+
+        assert_eq!(
+            compute_storage_layout(&["uint256", "(uint128,uint128,uint8)", "uint8"])
+                .unwrap()
+                .iter()
+                .map(|s| s.slot)
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 3]
+        );
+        assert!(compute_storage_layout(&["foo"]).is_err());
+    }
+
+    #[test]
+    fn test_dynamic_members_in_place() {
+        let slots = |types: &[&str]| -> Vec<usize> {
+            compute_storage_layout(types)
+                .unwrap()
+                .iter()
+                .map(|s| s.slot)
+                .collect()
+        };
+
+        assert_eq!(slots(&["(uint256,string)", "uint256"]), vec![0, 2]);
+        assert_eq!(slots(&["string[2]", "uint256"]), vec![0, 2]);
+        assert_eq!(slots(&["(uint8,uint256[])[2]", "bool"]), vec![0, 4]);
+        assert_eq!(slots(&["bytes", "uint256[]", "uint8"]), vec![0, 1, 2]);
+    }
+}